@@ -7,4 +7,6 @@ glslc shaders/backdrop.vert -o shaders/bin/backdrop_vert.spv
 glslc shaders/text.frag -o shaders/bin/text_frag.spv
 glslc shaders/text.vert -o shaders/bin/text_vert.spv
 glslc shaders/button.frag -o shaders/bin/button_frag.spv
-glslc shaders/button.vert -o shaders/bin/button_vert.spv
\ No newline at end of file
+glslc shaders/button.vert -o shaders/bin/button_vert.spv
+glslc shaders/particle.vert -o shaders/bin/particle_vert.spv
+glslc shaders/particle.frag -o shaders/bin/particle_frag.spv
\ No newline at end of file
@@ -14,6 +14,10 @@ in vec4 gl_FragCoord;
 struct InstanceDat {
     uvec4 col;
     uvec2 pos;
+
+    float flash;
+    float dissolve;
+    float offset_y;
 };
 
 layout(set = 0, binding = 8) readonly buffer u_instance{
@@ -0,0 +1,13 @@
+#version 450
+
+layout (location = 0) in vec2 texCoords;
+layout (location = 1) in vec4 color;
+
+layout (binding = 1) uniform sampler2D textures[3];
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    // the same block texture as the tetrominos, fragments of them
+    outColor = texture(textures[0], texCoords) * color;
+}
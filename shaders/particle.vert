@@ -0,0 +1,26 @@
+#version 450
+
+layout(location = 0) in vec2 inVertex;
+
+layout(location = 1) in vec2 pos;
+layout(location = 2) in float size;
+layout(location = 3) in vec4 color;
+
+layout(binding = 6) uniform u_projection{
+    layout(row_major) mat4 proj;
+} proj; 
+
+layout(location = 0) out vec2 texCoords;
+layout(location = 1) out vec4 outColor;
+
+void main() {
+    float scale_factor = 50;
+
+    // centred on the particle, slightly in front of the blocks
+    vec2 vertex = (inVertex - vec2(0.5)) * size;
+
+    gl_Position = proj.proj * vec4((vertex + pos)*scale_factor, -0.3, 1.0); 
+
+    texCoords = inVertex;
+    outColor = color;
+}
@@ -5,6 +5,8 @@ layout (location = 1) in flat uint texID;
 layout (location = 2) in vec4 color;
 layout (location = 3) in flat uint instance_count;
 layout (location = 4) in float scale_factor;
+layout (location = 5) in float flash;
+layout (location = 6) in float dissolve;
 
 layout (binding = 1) uniform sampler2D textures[3];
 
@@ -17,6 +19,10 @@ in vec4 gl_FragCoord;
 struct InstanceDat {
     uvec4 col;
     uvec2 pos;
+
+    float flash;
+    float dissolve;
+    float offset_y;
 };
 
 layout(set = 0, binding = 8) readonly buffer u_instance{
@@ -39,4 +45,7 @@ void main() {
     }
 
     outColor = texture(textures[texID], texCoords) * base_color / vec4(255.0f, 255.0f, 255.0f, 255.0f);
+
+    outColor.rgb = mix(outColor.rgb, vec3(1.0), flash);
+    outColor.a *= 1.0 - dissolve;
 }
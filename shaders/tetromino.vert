@@ -14,6 +14,10 @@ layout(binding = 6) uniform u_projection{
 struct InstanceDat {
     uvec4 col;
     uvec2 pos;
+
+    float flash;
+    float dissolve;
+    float offset_y;
 };
 
 layout(set = 0, binding = 8) readonly buffer u_instance{
@@ -26,11 +30,19 @@ layout(location = 1) out flat uint texID;
 layout(location = 2) out vec4 outColor;
 layout(location = 3) out uint o_instance_count;
 layout(location = 4) out float o_scale_factor;
+layout(location = 5) out float o_flash;
+layout(location = 6) out float o_dissolve;
 
 void main() {
     float scale_factor = 50;
 
-    gl_Position = proj.proj * vec4((inVertex + instance.dat[gl_InstanceIndex].pos)*scale_factor, -0.2, 1.0); 
+    InstanceDat dat = instance.dat[gl_InstanceIndex];
+
+    // dissolving blocks shrink towards their centre
+    vec2 vertex = (inVertex - vec2(0.5)) * (1.0 - dat.dissolve) + vec2(0.5);
+    vec2 pos = vec2(dat.pos) + vec2(0.0, dat.offset_y);
+
+    gl_Position = proj.proj * vec4((vertex + pos)*scale_factor, -0.2, 1.0); 
 
     outColor = instance.dat[gl_InstanceIndex].col;
     texCoords = inVertex; 
@@ -38,4 +50,6 @@ void main() {
 
     o_instance_count = instance_count;
     o_scale_factor = scale_factor;
+    o_flash = dat.flash;
+    o_dissolve = dat.dissolve;
 }
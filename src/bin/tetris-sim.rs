@@ -0,0 +1,257 @@
+#![warn(clippy::pedantic)]
+
+// Plays whole games without a window or a Vulkan device and prints per-game and mean statistics
+
+use rand::{rngs::StdRng, SeedableRng};
+use serde::Serialize;
+use std::{env, process};
+
+use vk_tetris::game::{Bot, Controller, GameMode, GameState, InputScript, InputSource, Playfield, Skin, TbpBot};
+
+const USAGE: &str = "usage: tetris-sim [--games N] [--seed S] [--mode MODE] [--script FILE] [--bot-config FILE] [--tbp-config FILE] \
+[--max-pieces N] [--max-time MS] [--frame MS] [--entry-delay MS] [--line-clear-delay MS] [--format csv|json]";
+
+#[derive(Clone, Copy)]
+enum Format {
+    Csv,
+    Json,
+}
+
+struct Options {
+    games: u64,
+    seed: u64,
+    mode: GameMode,
+    script: Option<String>,
+    bot_config: String,
+    tbp_config: Option<String>,
+    max_pieces: u32,
+    max_time: u128,
+    frame: u128,
+    entry_delay: u32,
+    line_clear_delay: u32,
+    format: Format,
+}
+
+#[derive(Serialize)]
+struct GameStats {
+    seed: u64,
+    lines: u32,
+    score: u32,
+    pieces: u32,
+    finesse_faults: u32,
+    average_height: f32,
+    length_ms: u128,
+    // whether the mode's goal was reached, as opposed to topping out or hitting a limit
+    completed: bool,
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    games: &'a [GameStats],
+    mean: MeanStats,
+}
+
+#[derive(Serialize)]
+struct MeanStats {
+    lines: f32,
+    score: f32,
+    pieces: f32,
+    finesse_faults: f32,
+    average_height: f32,
+    length_ms: f32,
+}
+
+// bad arguments are the user's mistake rather than a bug, so they get the usage instead of a panic
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{message}\n{USAGE}");
+    process::exit(2);
+}
+
+fn parse_value<T: std::str::FromStr>(args: &mut impl Iterator<Item = String>, flag: &str) -> T {
+    args.next()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_else(|| exit_with_usage(&format!("Missing or invalid value for {flag}")))
+}
+
+fn parse_options() -> Options {
+    let mut options = Options {
+        games: 100,
+        seed: 0,
+        mode: GameMode::Marathon,
+        script: None,
+        bot_config: String::from("conf/bot.json"),
+        tbp_config: None,
+        max_pieces: 1000,
+        max_time: 600_000,
+        frame: 16,
+        entry_delay: 0,
+        line_clear_delay: 0,
+        format: Format::Csv,
+    };
+
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--games" => options.games = parse_value(&mut args, &arg),
+            "--seed" => options.seed = parse_value(&mut args, &arg),
+            "--mode" => {
+                let name: String = parse_value(&mut args, &arg);
+                options.mode = GameMode::from_name(&name).unwrap_or_else(|| exit_with_usage(&format!("Unknown mode {name}")));
+            }
+            "--script" => options.script = Some(parse_value(&mut args, &arg)),
+            "--bot-config" => options.bot_config = parse_value(&mut args, &arg),
+            "--tbp-config" => options.tbp_config = Some(parse_value(&mut args, &arg)),
+            "--max-pieces" => options.max_pieces = parse_value(&mut args, &arg),
+            "--max-time" => options.max_time = parse_value(&mut args, &arg),
+            "--frame" => options.frame = parse_value(&mut args, &arg),
+            "--entry-delay" => options.entry_delay = parse_value(&mut args, &arg),
+            "--line-clear-delay" => options.line_clear_delay = parse_value(&mut args, &arg),
+            "--format" => {
+                options.format = match parse_value::<String>(&mut args, &arg).as_str() {
+                    "csv" => Format::Csv,
+                    "json" => Format::Json,
+                    other => exit_with_usage(&format!("Unknown format {other}")),
+                }
+            }
+            _ => exit_with_usage(&format!("Unknown argument {arg}")),
+        }
+    }
+
+    if options.frame == 0 {
+        exit_with_usage("--frame has to be at least 1 ms");
+    }
+
+    options
+}
+
+fn create_bot(options: &Options) -> Box<dyn Controller> {
+    let Some(path) = &options.tbp_config else {
+        return Box::new(Bot::new(&options.bot_config));
+    };
+
+    let mut tbp_bot = TbpBot::from_config(path)
+        .unwrap_or_else(|| exit_with_usage(&format!("The TBP bot in {path} is disabled or failed to start")));
+
+    // the simulated clock doesn't wait for the bot to think, so the bot has to be waited on instead
+    tbp_bot.set_blocking(true);
+
+    Box::new(tbp_bot)
+}
+
+// Steps the playfield on a simulated clock until it tops out or hits one of the limits
+fn run_game(seed: u64, skin: &Skin, options: &Options) -> GameStats {
+    let mut script = options.script.as_deref().map(InputScript::load);
+
+    // a recording is played back in the mode and with the pieces it was recorded with
+    let seed = script.as_ref().and_then(InputScript::get_seed).unwrap_or(seed);
+    let mode = script.as_ref().and_then(InputScript::get_mode).map_or(options.mode, |name| {
+        GameMode::from_name(name).unwrap_or_else(|| panic!("Unknown mode {name} in the input script"))
+    });
+
+    let mut playfield = Playfield::new(StdRng::seed_from_u64(seed), skin.palette.clone(), create_bot(options));
+    playfield.set_delays(options.entry_delay, options.line_clear_delay);
+
+    // started the same way the board starts a game, which is also when the walls go up
+    playfield.set_big(mode.get_block_scale() > 1);
+    playfield.set_seed(seed);
+    playfield.reset_game();
+
+    if script.is_none() {
+        playfield.set_autoplay(true);
+    }
+
+    let mut curr = 0u128;
+    let mut height_sum = 0u64;
+    let mut previous_pieces = 0u32;
+
+    while matches!(playfield.get_game_state(), GameState::RUNNING)
+        && playfield.get_pieces_placed() < options.max_pieces
+        && curr < options.max_time
+        && !mode.is_complete(playfield.get_lines_cleared(), curr as u64)
+    {
+        curr += options.frame;
+
+        let actions = match &mut script {
+            Some(script) => script.poll(curr),
+            None => Vec::new(),
+        };
+
+        playfield.update(&actions, curr);
+        playfield.drain_events();
+
+        // the height is sampled once per locked piece, after its lines have been cleared
+        if playfield.get_pieces_placed() != previous_pieces {
+            previous_pieces = playfield.get_pieces_placed();
+            height_sum += u64::from(playfield.get_stack_height());
+        }
+    }
+
+    let pieces = playfield.get_pieces_placed();
+
+    GameStats {
+        seed,
+        lines: playfield.get_lines_cleared(),
+        score: *playfield.get_score().lock().expect("Failed to lock the score"),
+        pieces,
+        finesse_faults: playfield.get_finesse_faults(),
+        average_height: if pieces == 0 {
+            0.0
+        } else {
+            height_sum as f32 / pieces as f32
+        },
+        length_ms: curr,
+        completed: mode.is_complete(playfield.get_lines_cleared(), curr as u64),
+    }
+}
+
+fn get_mean(games: &[GameStats]) -> MeanStats {
+    let count = games.len().max(1) as f32;
+
+    MeanStats {
+        lines: games.iter().map(|game| game.lines as f32).sum::<f32>() / count,
+        score: games.iter().map(|game| game.score as f32).sum::<f32>() / count,
+        pieces: games.iter().map(|game| game.pieces as f32).sum::<f32>() / count,
+        finesse_faults: games.iter().map(|game| game.finesse_faults as f32).sum::<f32>() / count,
+        average_height: games.iter().map(|game| game.average_height).sum::<f32>() / count,
+        length_ms: games.iter().map(|game| game.length_ms as f32).sum::<f32>() / count,
+    }
+}
+
+fn print_csv(games: &[GameStats], mean: &MeanStats) {
+    println!("seed,lines,score,pieces,finesse_faults,average_height,length_ms,completed");
+
+    for game in games {
+        println!(
+            "{},{},{},{},{},{:.3},{},{}",
+            game.seed, game.lines, game.score, game.pieces, game.finesse_faults, game.average_height, game.length_ms, game.completed
+        );
+    }
+
+    println!(
+        "mean,{:.3},{:.3},{:.3},{:.3},{:.3},{:.3}",
+        mean.lines, mean.score, mean.pieces, mean.finesse_faults, mean.average_height, mean.length_ms
+    );
+}
+
+fn main() {
+    let options = parse_options();
+
+    // pieces get the same colours the game would give them
+    let skin = Skin::from_config("conf/skin.json");
+
+    let games: Vec<GameStats> = (options.seed..options.seed + options.games)
+        .map(|seed| run_game(seed, &skin, &options))
+        .collect();
+
+    let mean = get_mean(&games);
+
+    match options.format {
+        Format::Csv => print_csv(&games, &mean),
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&Report { games: &games, mean })
+                .expect("Failed to serialize the simulation report")
+        ),
+    }
+}
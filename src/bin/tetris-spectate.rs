@@ -0,0 +1,103 @@
+#![warn(clippy::pedantic)]
+
+// Terminal viewer for the spectator stream published by the game (see conf/spectator.json)
+
+use serde::Deserialize;
+use std::{
+    env,
+    fmt::Write as _,
+    io::{self, BufRead, BufReader, Write},
+    net::TcpStream,
+    process,
+};
+
+const USAGE: &str = "usage: tetris-spectate [ADDRESS]";
+
+#[derive(Deserialize)]
+struct PieceSnapshot {
+    shape: String,
+    color: [u8; 4],
+    cells: Vec<[u8; 2]>,
+}
+
+#[derive(Deserialize)]
+struct BoardSnapshot {
+    state: String,
+    score: u32,
+    grid: Vec<Vec<[u8; 4]>>,
+    piece: PieceSnapshot,
+}
+
+fn draw_cell(frame: &mut String, color: [u8; 4]) {
+    if color == [0; 4] {
+        frame.push_str(" .");
+        return;
+    }
+
+    let _ = write!(frame, "\x1b[38;2;{};{};{}m██\x1b[0m", color[0], color[1], color[2]);
+}
+
+fn render(snapshot: &BoardSnapshot) -> String {
+    let mut grid = snapshot.grid.clone();
+
+    for cell in &snapshot.piece.cells {
+        let (x, y) = (cell[0] as usize, cell[1] as usize);
+
+        if let Some(row) = grid.get_mut(y) {
+            if let Some(block) = row.get_mut(x) {
+                *block = snapshot.piece.color;
+            }
+        }
+    }
+
+    // clear the screen and move the cursor home so every snapshot redraws in place
+    let mut frame = String::from("\x1b[2J\x1b[H");
+
+    let width = grid.first().map_or(0, Vec::len);
+    let border = "--".repeat(width);
+
+    let _ = writeln!(frame, "+{border}+");
+    for row in &grid {
+        frame.push('|');
+        for block in row {
+            draw_cell(&mut frame, *block);
+        }
+        frame.push_str("|\n");
+    }
+    let _ = writeln!(frame, "+{border}+");
+
+    let _ = writeln!(frame, "SCORE {}", snapshot.score);
+    let _ = writeln!(frame, "PIECE {}", snapshot.piece.shape);
+    let _ = writeln!(frame, "STATE {}", snapshot.state);
+
+    frame
+}
+
+fn main() {
+    let address = env::args().nth(1).unwrap_or(String::from("127.0.0.1:7878"));
+
+    // the game not running or spectating being off isn't a bug, so it's reported without a panic
+    let stream = TcpStream::connect(&address).unwrap_or_else(|err| {
+        eprintln!("Failed to connect to the spectator stream at {address}: {err}\n{USAGE}");
+        process::exit(2);
+    });
+
+    let mut stdout = io::stdout();
+
+    for line in BufReader::new(stream).lines() {
+        let line = line.expect("Lost the connection to the game");
+
+        let snapshot: BoardSnapshot = match serde_json::from_str(&line) {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                eprintln!("Skipping a malformed snapshot: {err}");
+                continue;
+            }
+        };
+
+        stdout
+            .write_all(render(&snapshot).as_bytes())
+            .expect("Failed to write to stdout");
+        stdout.flush().expect("Failed to flush stdout");
+    }
+}
@@ -0,0 +1,92 @@
+// Everything is laid out on a canvas of this size, in canvas units, whatever the window's size and DPI
+pub const CANVAS_WIDTH: u32 = 720;
+pub const CANVAS_HEIGHT: u32 = 1280;
+
+// Where the canvas lands in the framebuffer: as large as it fits without stretching, centred, with black bars around it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Letterbox {
+    pub x: f32,
+    pub y: f32,
+    // framebuffer pixels per canvas unit
+    pub scale: f32,
+}
+
+impl Letterbox {
+    pub fn new(framebuffer: (u32, u32)) -> Letterbox {
+        let scale = (framebuffer.0 as f32 / CANVAS_WIDTH as f32).min(framebuffer.1 as f32 / CANVAS_HEIGHT as f32);
+
+        Letterbox {
+            x: (framebuffer.0 as f32 - CANVAS_WIDTH as f32 * scale) / 2.0,
+            y: (framebuffer.1 as f32 - CANVAS_HEIGHT as f32 * scale) / 2.0,
+            scale,
+        }
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (CANVAS_WIDTH as f32 * self.scale, CANVAS_HEIGHT as f32 * self.scale)
+    }
+
+    // None on the bars around the canvas
+    pub fn to_canvas(self, pos: (f64, f64)) -> Option<(u32, u32)> {
+        if self.scale <= 0.0 {
+            return None;
+        }
+
+        let x = (pos.0 as f32 - self.x) / self.scale;
+        let y = (pos.1 as f32 - self.y) / self.scale;
+
+        if x < 0.0 || y < 0.0 || x >= CANVAS_WIDTH as f32 || y >= CANVAS_HEIGHT as f32 {
+            return None;
+        }
+
+        Some((x as u32, y as u32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Letterbox;
+
+    #[test]
+    fn wide_framebuffers_get_bars_on_the_sides() {
+        let letterbox = Letterbox::new((1920, 1080));
+
+        assert_eq!(letterbox, Letterbox { x: 656.25, y: 0.0, scale: 0.84375 });
+        assert_eq!(letterbox.get_size(), (607.5, 1080.0));
+    }
+
+    #[test]
+    fn tall_framebuffers_get_bars_above_and_below() {
+        let letterbox = Letterbox::new((720, 1600));
+
+        assert_eq!(letterbox, Letterbox { x: 0.0, y: 160.0, scale: 1.0 });
+        assert_eq!(letterbox.get_size(), (720.0, 1280.0));
+    }
+
+    #[test]
+    fn the_bars_are_outside_the_canvas() {
+        let letterbox = Letterbox::new((720, 1600));
+
+        assert_eq!(letterbox.to_canvas((360.0, 100.0)), None);
+        assert_eq!(letterbox.to_canvas((360.0, 1500.0)), None);
+        assert_eq!(letterbox.to_canvas((360.0, 160.0)), Some((360, 0)));
+        assert_eq!(letterbox.to_canvas((719.0, 1439.0)), Some((719, 1279)));
+
+        let letterbox = Letterbox::new((1920, 1080));
+
+        assert_eq!(letterbox.to_canvas((600.0, 540.0)), None);
+        assert_eq!(letterbox.to_canvas((1300.0, 540.0)), None);
+        assert_eq!(letterbox.to_canvas((960.0, 540.0)), Some((360, 640)));
+
+        // a minimized window has no canvas at all
+        assert_eq!(Letterbox::new((0, 0)).to_canvas((0.0, 0.0)), None);
+    }
+
+    #[test]
+    fn a_doubled_framebuffer_doubles_the_scale() {
+        let letterbox = Letterbox::new((1440, 2560));
+
+        assert_eq!(letterbox, Letterbox { x: 0.0, y: 0.0, scale: 2.0 });
+        assert_eq!(letterbox.to_canvas((1439.0, 2559.0)), Some((719, 1279)));
+    }
+}
@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+use kira::{sound::static_sound::StaticSoundData, Decibels};
+
+use super::{Bus, SoundEvent};
+
+// What the game asked to hear, whether or not the bank had a file for it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cue {
+    // a layer of the soundtrack, by its place in the music config
+    Music(usize),
+    Sound(SoundEvent),
+}
+
+// Where the audio manager sends its sounds and volumes, so the game runs the same with or without a sound card
+pub trait AudioBackend {
+    // the data is None when the bank has no file for the cue
+    fn play(&mut self, cue: Cue, bus: Bus, data: Option<StaticSoundData>);
+
+    fn set_master_volume(&mut self, volume: Decibels);
+
+    fn set_bus_volume(&mut self, bus: Bus, volume: Decibels, fade: Duration);
+
+    // music layers loop on the music bus until the game closes and can still be faded and sped up after they start
+    fn play_music(&mut self, layer: usize, data: Option<StaticSoundData>);
+
+    fn set_music_layer(&mut self, layer: usize, volume: Decibels, playback_rate: f64, fade: Duration);
+}
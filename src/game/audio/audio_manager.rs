@@ -1,20 +1,223 @@
-use super::Sound;
+use std::time::Duration;
+
+use kira::Decibels;
+
+use super::{AudioBackend, Cue, KiraBackend, Music, NullBackend, SoundBank, SoundEvent};
+use crate::game::board::BoardEvent;
+use crate::game::settings::AudioSettings;
+
+// Which sub-track a sound is played on, each has its own volume under the master one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bus {
+    Music,
+    Sfx,
+    Ui,
+}
+
+// how long the music takes to fade down at game over and back up after
+const DUCK_FADE: Duration = Duration::from_millis(400);
 
 pub struct AudioManager {
-    manager: kira::AudioManager,
+    backend: Box<dyn AudioBackend>,
+
+    settings: AudioSettings,
+    ducked: bool,
+
+    bank: SoundBank,
+    // clears in a row, counted the same way the stats count combos
+    combo: u32,
+    last_piece_cleared: bool,
+
+    music: Option<Music>,
+    level: u32,
+}
+
+fn to_decibels(amplitude: f32) -> Decibels {
+    if amplitude <= 0.0 {
+        return Decibels::SILENCE;
+    }
+
+    Decibels((20.0 * amplitude.log10()).max(Decibels::SILENCE.0))
 }
 
 impl AudioManager {
-    pub fn new() -> AudioManager {
-        let manager = kira::AudioManager::<kira::DefaultBackend>::
-         new(kira::AudioManagerSettings::default())
-         .expect("Failed to create an audio manager");
+    // without an audio device the game carries on silently
+    pub fn new(settings: &AudioSettings) -> AudioManager {
+        let backend: Box<dyn AudioBackend> = match KiraBackend::new() {
+            Ok(backend) => Box::new(backend),
+            Err(error) => {
+                println!("No audio output, the game will be silent: {}", error);
+                Box::new(NullBackend)
+            }
+        };
+
+        AudioManager::with_backend(settings, backend)
+    }
+
+    pub fn with_backend(settings: &AudioSettings, backend: Box<dyn AudioBackend>) -> AudioManager {
+        let mut audio_manager = AudioManager {
+            backend,
+            settings: settings.clone(),
+            ducked: false,
+            bank: SoundBank::load("conf/sounds.json"),
+            combo: 0,
+            last_piece_cleared: false,
+            music: Music::from_config("conf/music.json"),
+            level: 0,
+        };
 
-        AudioManager { manager }
+        audio_manager.apply_settings(settings);
+
+        audio_manager
+    }
+
+    pub fn play_event(&mut self, event: SoundEvent) {
+        let data = self.bank.get_sound(event, self.combo);
+
+        self.backend.play(Cue::Sound(event), event.get_bus(), data);
     }
 
-    pub fn play(&mut self, sound: &mut Sound) {
-        self.manager.play(sound.get_data().clone())
-         .expect("Failed to play sound");
+    pub fn start_music(&mut self) {
+        if let Some(music) = &mut self.music {
+            music.start(self.backend.as_mut());
+        }
     }
-}
\ No newline at end of file
+
+    // the stack height goes from 0 (empty) to 1 (topped out)
+    pub fn update_music(&mut self, stack_height: f32, lines: u32) {
+        let level = self.bank.get_level(lines);
+
+        if level > self.level {
+            self.play_event(SoundEvent::LevelUp);
+        }
+
+        self.level = level;
+
+        if let Some(music) = &mut self.music {
+            music.update(self.backend.as_mut(), stack_height, level);
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &BoardEvent) {
+        match event {
+            BoardEvent::PieceMoved => self.play_event(SoundEvent::Move),
+            BoardEvent::PieceRotated => self.play_event(SoundEvent::Rotate),
+            BoardEvent::RotationFailed => self.play_event(SoundEvent::RotateFail),
+            BoardEvent::SoftDropped => self.play_event(SoundEvent::SoftDrop),
+            BoardEvent::HardDropped { .. } => self.play_event(SoundEvent::HardDrop),
+
+            BoardEvent::PieceLocked(_) => {
+                if !self.last_piece_cleared {
+                    self.combo = 0;
+                }
+
+                self.last_piece_cleared = false;
+                self.play_event(SoundEvent::Lock);
+            }
+
+            BoardEvent::LinesCleared { rows, t_spin } => {
+                self.combo += 1;
+                self.last_piece_cleared = true;
+
+                self.play_event(match (rows.len(), t_spin) {
+                    (_, true) => SoundEvent::TSpin,
+                    (1, _) => SoundEvent::Single,
+                    (2, _) => SoundEvent::Double,
+                    (3, _) => SoundEvent::Triple,
+                    _ => SoundEvent::Tetris,
+                });
+
+                if self.combo > 1 {
+                    self.play_event(SoundEvent::Combo);
+                }
+            }
+
+            BoardEvent::GameOver => self.play_event(SoundEvent::GameOver),
+
+            BoardEvent::GameReset => {
+                self.combo = 0;
+                self.last_piece_cleared = false;
+            }
+
+            _ => (),
+        }
+    }
+
+    fn get_music_volume(&self) -> Decibels {
+        let volume = to_decibels(self.settings.music.get_amplitude());
+
+        if !self.ducked {
+            return volume;
+        }
+
+        Decibels((volume.0 - self.settings.game_over_duck).max(Decibels::SILENCE.0))
+    }
+
+    pub fn apply_settings(&mut self, settings: &AudioSettings) {
+        self.settings = settings.clone();
+
+        self.backend.set_master_volume(to_decibels(settings.master.get_amplitude()));
+        let music_volume = self.get_music_volume();
+
+        self.backend.set_bus_volume(Bus::Music, music_volume, Duration::ZERO);
+        self.backend.set_bus_volume(Bus::Sfx, to_decibels(settings.sfx.get_amplitude()), Duration::ZERO);
+        self.backend.set_bus_volume(Bus::Ui, to_decibels(settings.ui.get_amplitude()), Duration::ZERO);
+    }
+
+    pub fn set_ducked(&mut self, ducked: bool) {
+        if self.ducked == ducked {
+            return;
+        }
+
+        self.ducked = ducked;
+        let volume = self.get_music_volume();
+        self.backend.set_bus_volume(Bus::Music, volume, DUCK_FADE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AudioManager;
+    use crate::game::audio::{Cue, RecordingBackend, SoundEvent};
+    use crate::game::board::{BoardEvent, ClearedRow};
+    use crate::game::settings::AudioSettings;
+    use crate::types::PLAYFIELD_WIDTH;
+
+    fn get_clear(lines: usize) -> BoardEvent {
+        let rows = (0..lines).map(|y| ClearedRow { y, blocks: [[1; 4]; PLAYFIELD_WIDTH] }).collect();
+
+        BoardEvent::LinesCleared { rows, t_spin: false }
+    }
+
+    #[test]
+    fn board_events_trigger_their_sounds() {
+        let backend = RecordingBackend::new();
+        let log = backend.get_log();
+
+        let mut audio_manager = AudioManager::with_backend(&AudioSettings::load("conf/audio.json"), Box::new(backend));
+
+        audio_manager.handle_event(&BoardEvent::PieceMoved);
+        audio_manager.handle_event(&BoardEvent::RotationFailed);
+        audio_manager.handle_event(&BoardEvent::KeyPressed);
+
+        // a second clear in a row is a combo
+        audio_manager.handle_event(&get_clear(1));
+        audio_manager.handle_event(&get_clear(2));
+
+        // the level only goes up once for all the lines it takes
+        audio_manager.update_music(0.5, 10);
+        audio_manager.update_music(0.5, 11);
+
+        audio_manager.handle_event(&BoardEvent::GameOver);
+
+        assert_eq!(*log.lock().unwrap(), vec![
+            Cue::Sound(SoundEvent::Move),
+            Cue::Sound(SoundEvent::RotateFail),
+            Cue::Sound(SoundEvent::Single),
+            Cue::Sound(SoundEvent::Double),
+            Cue::Sound(SoundEvent::Combo),
+            Cue::Sound(SoundEvent::LevelUp),
+            Cue::Sound(SoundEvent::GameOver),
+        ]);
+    }
+}
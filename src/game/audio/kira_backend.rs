@@ -0,0 +1,90 @@
+use std::{collections::HashMap, time::Duration};
+
+use kira::{sound::static_sound::{StaticSoundData, StaticSoundHandle}, track::{TrackBuilder, TrackHandle}, Decibels, PlaybackRate, Tween};
+
+use super::{AudioBackend, Bus, Cue};
+
+// Plays through the system's default output with a sub-track per bus
+pub struct KiraBackend {
+    manager: kira::AudioManager,
+
+    music: TrackHandle,
+    sfx: TrackHandle,
+    ui: TrackHandle,
+
+    music_layers: HashMap<usize, StaticSoundHandle>,
+}
+
+fn get_tween(duration: Duration) -> Tween {
+    Tween { duration, ..Default::default() }
+}
+
+impl KiraBackend {
+    // fails without an output device, on headless machines for example
+    pub fn new() -> Result<KiraBackend, String> {
+        let mut manager = kira::AudioManager::<kira::DefaultBackend>::
+         new(kira::AudioManagerSettings::default())
+         .map_err(|error| error.to_string())?;
+
+        let mut add_track = || manager
+         .add_sub_track(TrackBuilder::new())
+         .map_err(|error| error.to_string());
+
+        let music = add_track()?;
+        let sfx = add_track()?;
+        let ui = add_track()?;
+
+        Ok(KiraBackend { manager, music, sfx, ui, music_layers: HashMap::new() })
+    }
+
+    fn get_track(&mut self, bus: Bus) -> &mut TrackHandle {
+        match bus {
+            Bus::Music => &mut self.music,
+            Bus::Sfx => &mut self.sfx,
+            Bus::Ui => &mut self.ui,
+        }
+    }
+}
+
+impl AudioBackend for KiraBackend {
+    fn play(&mut self, cue: Cue, bus: Bus, data: Option<StaticSoundData>) {
+        let Some(data) = data else {
+            return;
+        };
+
+        // running out of sound slots only loses this one sound
+        if let Err(error) = self.get_track(bus).play(data) {
+            println!("Failed to play {:?}: {:?}", cue, error);
+        }
+    }
+
+    fn set_master_volume(&mut self, volume: Decibels) {
+        self.manager.main_track().set_volume(volume, Tween::default());
+    }
+
+    fn set_bus_volume(&mut self, bus: Bus, volume: Decibels, fade: Duration) {
+        self.get_track(bus).set_volume(volume, get_tween(fade));
+    }
+
+    fn play_music(&mut self, layer: usize, data: Option<StaticSoundData>) {
+        let Some(data) = data else {
+            return;
+        };
+
+        match self.music.play(data) {
+            Ok(handle) => {
+                self.music_layers.insert(layer, handle);
+            }
+            Err(error) => println!("Failed to play {:?}: {:?}", Cue::Music(layer), error),
+        }
+    }
+
+    fn set_music_layer(&mut self, layer: usize, volume: Decibels, playback_rate: f64, fade: Duration) {
+        let Some(handle) = self.music_layers.get_mut(&layer) else {
+            return;
+        };
+
+        handle.set_volume(volume, get_tween(fade));
+        handle.set_playback_rate(PlaybackRate(playback_rate), get_tween(fade));
+    }
+}
@@ -1,5 +1,23 @@
 mod audio_manager;
-pub use audio_manager::AudioManager;
+pub use audio_manager::{AudioManager, Bus};
+
+mod audio_backend;
+pub use audio_backend::{AudioBackend, Cue};
+
+mod kira_backend;
+pub use kira_backend::KiraBackend;
+
+mod null_backend;
+pub use null_backend::NullBackend;
+
+mod recording_backend;
+pub use recording_backend::RecordingBackend;
+
+mod music;
+pub use music::Music;
 
 mod sound;
-pub use sound::Sound;
\ No newline at end of file
+pub use sound::Sound;
+
+mod sound_bank;
+pub use sound_bank::{SoundBank, SoundEvent};
\ No newline at end of file
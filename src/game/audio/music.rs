@@ -0,0 +1,186 @@
+use kira::Decibels;
+use serde::Deserialize;
+use std::{fs, time::Duration};
+
+use super::{AudioBackend, Sound};
+
+// A stem that plays while the stack is at least this high, heights go from 0 (empty) to 1 (topped out).
+// Danger layers replace the others once the stack gets near the top.
+#[derive(Deserialize, Debug)]
+struct LayerConfig {
+    pub file: String,
+    pub volume: f32,
+    pub from_height: f32,
+    #[serde(default)]
+    pub danger: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    pub enabled: bool,
+    pub layers: Vec<LayerConfig>,
+    pub danger_height: f32,
+
+    // how much faster the music plays with every level, up to the max
+    pub tempo_per_level: f64,
+    pub max_tempo: f64,
+    // on top of the level's tempo while in danger
+    pub danger_tempo: f64,
+
+    // in milliseconds, how long layers take to fade in and out and the tempo takes to change
+    pub crossfade: u64,
+}
+
+// The soundtrack as layers that all loop together from the start, so fading one in keeps it in time with the rest.
+// Only the base stem (music.wav, 32 beats at 140 bpm) ships with the game, more layers and a danger layer of the
+// same length are optional and go in conf/music.json.
+pub struct Music {
+    config: Config,
+    layers: Vec<Option<Sound>>,
+
+    // what the layers were last set to, nothing is sent again until it changes
+    active: Vec<bool>,
+    tempo: f64,
+}
+
+impl Music {
+    fn load_config(path: &str) -> Config {
+        let contents = fs::read_to_string(path).expect("Failed to read the music config file");
+
+        serde_json::from_str(&contents).expect("Could not parse music JSON config")
+    }
+
+    pub fn from_config(path: &str) -> Option<Music> {
+        let config = Music::load_config(path);
+
+        if !config.enabled {
+            return None;
+        }
+
+        let layers = config.layers.iter().map(|layer| Sound::load(&layer.file, layer.volume, true)).collect();
+
+        Some(Music { active: Vec::new(), tempo: 1.0, config, layers })
+    }
+
+    fn get_active(&self, stack_height: f32) -> Vec<bool> {
+        let danger = stack_height >= self.config.danger_height;
+
+        self.config
+            .layers
+            .iter()
+            .map(|layer| layer.danger == danger && stack_height >= layer.from_height)
+            .collect()
+    }
+
+    fn get_tempo(&self, stack_height: f32, level: u32) -> f64 {
+        let tempo = (1.0 + self.config.tempo_per_level * level as f64).min(self.config.max_tempo);
+
+        if stack_height >= self.config.danger_height {
+            return tempo * self.config.danger_tempo;
+        }
+
+        tempo
+    }
+
+    fn get_volume(&self, layer: usize, active: bool) -> Decibels {
+        if !active {
+            return Decibels::SILENCE;
+        }
+
+        Decibels(self.config.layers[layer].volume)
+    }
+
+    // every layer starts at once, the ones that shouldn't be heard on an empty stack start silent
+    pub fn start(&mut self, backend: &mut dyn AudioBackend) {
+        self.active = self.get_active(0.0);
+        self.tempo = 1.0;
+
+        let volumes: Vec<Decibels> = self.active.iter().enumerate().map(|(i, active)| self.get_volume(i, *active)).collect();
+
+        for (i, (layer, volume)) in self.layers.iter_mut().zip(volumes).enumerate() {
+            let data = layer.as_mut().map(|sound| sound.get_data().clone().volume(volume));
+
+            backend.play_music(i, data);
+        }
+    }
+
+    pub fn update(&mut self, backend: &mut dyn AudioBackend, stack_height: f32, level: u32) {
+        let active = self.get_active(stack_height);
+        let tempo = self.get_tempo(stack_height, level);
+
+        if active == self.active && tempo == self.tempo {
+            return;
+        }
+
+        let fade = Duration::from_millis(self.config.crossfade);
+
+        for (i, is_active) in active.iter().enumerate() {
+            backend.set_music_layer(i, self.get_volume(i, *is_active), tempo, fade);
+        }
+
+        self.active = active;
+        self.tempo = tempo;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kira::Decibels;
+
+    use super::Music;
+    use crate::game::audio::RecordingBackend;
+
+    // a base layer, one that comes in halfway up and a danger layer
+    const CONFIG: &str = r#"{
+        "enabled": true,
+        "layers": [
+            { "file": "base.wav", "volume": -20.0, "from_height": 0.0 },
+            { "file": "drums.wav", "volume": -22.0, "from_height": 0.5 },
+            { "file": "danger.wav", "volume": -18.0, "from_height": 0.0, "danger": true }
+        ],
+        "danger_height": 0.8,
+        "tempo_per_level": 0.02,
+        "max_tempo": 1.3,
+        "danger_tempo": 1.08,
+        "crossfade": 1500
+    }"#;
+
+    #[test]
+    fn layers_and_tempo_follow_the_stack_and_level() {
+        let mut backend = RecordingBackend::new();
+        let log = backend.get_music_log();
+
+        let config = serde_json::from_str(CONFIG).unwrap();
+        let mut music = Music { config, layers: vec![None, None, None], active: Vec::new(), tempo: 1.0 };
+
+        let silence = Decibels::SILENCE.0;
+        let level_5 = 1.0 + 0.02 * 5.0;
+
+        music.start(&mut backend);
+
+        // nothing changes until the stack or the level does
+        music.update(&mut backend, 0.2, 0);
+        assert!(log.lock().unwrap().is_empty());
+
+        music.update(&mut backend, 0.6, 0);
+        assert_eq!(*log.lock().unwrap(), vec![(0, -20.0, 1.0), (1, -22.0, 1.0), (2, silence, 1.0)]);
+        log.lock().unwrap().clear();
+
+        music.update(&mut backend, 0.6, 5);
+        assert_eq!(*log.lock().unwrap(), vec![(0, -20.0, level_5), (1, -22.0, level_5), (2, silence, level_5)]);
+        log.lock().unwrap().clear();
+
+        // only the danger layer plays near the top, a little faster still
+        music.update(&mut backend, 0.9, 5);
+        assert_eq!(*log.lock().unwrap(), vec![(0, silence, level_5 * 1.08), (1, silence, level_5 * 1.08), (2, -18.0, level_5 * 1.08)]);
+        log.lock().unwrap().clear();
+
+        // the level tempo stops at the max
+        music.update(&mut backend, 0.9, 100);
+        assert_eq!(*log.lock().unwrap(), vec![(0, silence, 1.3 * 1.08), (1, silence, 1.3 * 1.08), (2, -18.0, 1.3 * 1.08)]);
+        log.lock().unwrap().clear();
+
+        music.update(&mut backend, 0.1, 100);
+        assert_eq!(*log.lock().unwrap(), vec![(0, -20.0, 1.3), (1, silence, 1.3), (2, silence, 1.3)]);
+    }
+}
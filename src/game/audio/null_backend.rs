@@ -0,0 +1,20 @@
+use std::time::Duration;
+
+use kira::{sound::static_sound::StaticSoundData, Decibels};
+
+use super::{AudioBackend, Bus, Cue};
+
+// Plays nothing, for when there's no audio device to play on
+pub struct NullBackend;
+
+impl AudioBackend for NullBackend {
+    fn play(&mut self, _cue: Cue, _bus: Bus, _data: Option<StaticSoundData>) {}
+
+    fn set_master_volume(&mut self, _volume: Decibels) {}
+
+    fn set_bus_volume(&mut self, _bus: Bus, _volume: Decibels, _fade: Duration) {}
+
+    fn play_music(&mut self, _layer: usize, _data: Option<StaticSoundData>) {}
+
+    fn set_music_layer(&mut self, _layer: usize, _volume: Decibels, _playback_rate: f64, _fade: Duration) {}
+}
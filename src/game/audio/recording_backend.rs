@@ -0,0 +1,47 @@
+use std::{sync::{Arc, Mutex}, time::Duration};
+
+use kira::{sound::static_sound::StaticSoundData, Decibels};
+
+use super::{AudioBackend, Bus, Cue};
+
+// every change to a music layer as (layer, volume in decibels, playback rate)
+type MusicLog = Arc<Mutex<Vec<(usize, f32, f64)>>>;
+
+// Plays nothing but keeps every cue in order, the logs are shared so they can still be read once the backend is handed over
+#[derive(Default)]
+pub struct RecordingBackend {
+    log: Arc<Mutex<Vec<Cue>>>,
+    music_log: MusicLog,
+}
+
+impl RecordingBackend {
+    pub fn new() -> RecordingBackend {
+        RecordingBackend::default()
+    }
+
+    pub fn get_log(&self) -> Arc<Mutex<Vec<Cue>>> {
+        self.log.clone()
+    }
+
+    pub fn get_music_log(&self) -> MusicLog {
+        self.music_log.clone()
+    }
+}
+
+impl AudioBackend for RecordingBackend {
+    fn play(&mut self, cue: Cue, _bus: Bus, _data: Option<StaticSoundData>) {
+        self.log.lock().expect("Failed to lock").push(cue);
+    }
+
+    fn set_master_volume(&mut self, _volume: Decibels) {}
+
+    fn set_bus_volume(&mut self, _bus: Bus, _volume: Decibels, _fade: Duration) {}
+
+    fn play_music(&mut self, layer: usize, _data: Option<StaticSoundData>) {
+        self.log.lock().expect("Failed to lock").push(Cue::Music(layer));
+    }
+
+    fn set_music_layer(&mut self, layer: usize, volume: Decibels, playback_rate: f64, _fade: Duration) {
+        self.music_log.lock().expect("Failed to lock").push((layer, volume.0, playback_rate));
+    }
+}
@@ -5,16 +5,21 @@ pub struct Sound {
 }
 
 impl Sound {
-    pub fn new(path: &str, volume: f32, repeat: bool) -> Sound {
-        let mut data = StaticSoundData::from_file(path)
-         .expect(format!("Failed to parse {path}").as_str())
-         .volume(volume);
+    // a missing or broken file only costs its own sound
+    pub fn load(path: &str, volume: f32, repeat: bool) -> Option<Sound> {
+        let mut data = match StaticSoundData::from_file(path) {
+            Ok(data) => data.volume(volume),
+            Err(error) => {
+                println!("Failed to load {path}, it won't be played: {error}");
+                return None;
+            }
+        };
 
         if repeat {
             data = data.loop_region(0.0f64..(data.num_frames() as f64));
         }
 
-        Sound { data }
+        Some(Sound { data })
     }
 
     pub fn get_data(&mut self) -> &StaticSoundData {
@@ -0,0 +1,109 @@
+use kira::{sound::static_sound::StaticSoundData, PlaybackRate};
+use rand::prelude::*;
+use serde::Deserialize;
+use std::{collections::{BTreeMap, HashMap}, fs};
+
+use super::{Bus, Sound};
+
+// Everything the game can make a sound for, the bank maps each to a file
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+pub enum SoundEvent {
+    Move,
+    Rotate,
+    RotateFail,
+    // nothing raises this one yet, the game has no hold piece
+    Hold,
+    LevelUp,
+    SoftDrop,
+    HardDrop,
+    Lock,
+    Single,
+    Double,
+    Triple,
+    Tetris,
+    TSpin,
+    Combo,
+    GameOver,
+    MenuHover,
+    MenuClick,
+}
+
+impl SoundEvent {
+    pub fn get_bus(&self) -> Bus {
+        match self {
+            SoundEvent::MenuHover | SoundEvent::MenuClick => Bus::Ui,
+            _ => Bus::Sfx,
+        }
+    }
+}
+
+// The volume is in decibels, the pitch is randomly moved up or down by up to the variation (0.05 is 5%)
+#[derive(Deserialize, Debug)]
+struct SoundConfig {
+    pub file: String,
+    pub volume: f32,
+    #[serde(default)]
+    pub pitch_variation: f64,
+}
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    pub sounds: BTreeMap<SoundEvent, SoundConfig>,
+    // how much higher the combo sound gets with every clear in a row
+    pub combo_pitch_step: f64,
+    // the game has no levels of its own, the audio counts one every this many lines
+    pub lines_per_level: u32,
+}
+
+// The sounds from the bank file, events whose file is missing stay silent.
+// Only the lock sound ships with the game, the other events are optional and get a sound by adding them to
+// conf/sounds.json, e.g. "HardDrop": { "file": "sounds/hard_drop.wav", "volume": -2.0 }.
+pub struct SoundBank {
+    sounds: HashMap<SoundEvent, (Sound, f64)>,
+    combo_pitch_step: f64,
+    lines_per_level: u32,
+
+    rng: ThreadRng,
+}
+
+impl SoundBank {
+    fn load_config(path: &str) -> Config {
+        let contents = fs::read_to_string(path).expect("Failed to read the sound bank config file");
+
+        serde_json::from_str(&contents).expect("Could not parse sound bank JSON config")
+    }
+
+    pub fn load(path: &str) -> SoundBank {
+        let config = SoundBank::load_config(path);
+
+        let sounds = config
+            .sounds
+            .iter()
+            .filter_map(|(event, sound)| Some((*event, (Sound::load(&sound.file, sound.volume, false)?, sound.pitch_variation))))
+            .collect();
+
+        SoundBank {
+            sounds,
+            combo_pitch_step: config.combo_pitch_step,
+            lines_per_level: config.lines_per_level.max(1),
+            rng: rand::rng(),
+        }
+    }
+
+    pub fn get_level(&self, lines: u32) -> u32 {
+        lines / self.lines_per_level
+    }
+
+    // a combo of n clears in a row plays the combo sound n - 1 steps higher
+    pub fn get_sound(&mut self, event: SoundEvent, combo: u32) -> Option<StaticSoundData> {
+        let (sound, variation) = self.sounds.get_mut(&event)?;
+
+        let mut pitch = 1.0 + self.rng.random_range(-*variation..=*variation);
+
+        if event == SoundEvent::Combo {
+            pitch += self.combo_pitch_step * combo.saturating_sub(1) as f64;
+        }
+
+        Some(sound.get_data().clone().playback_rate(PlaybackRate(pitch)))
+    }
+}
@@ -0,0 +1,147 @@
+use super::*;
+use crate::types::*;
+
+const LOCK_FLASH_TIME: u128 = 150;
+const COLLAPSE_TIME: u128 = 150;
+const REVEAL_TIME: u128 = 1000;
+
+fn ease_out_cubic(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+fn ease_in_quad(t: f32) -> f32 {
+    t * t
+}
+
+fn get_progress(curr: u128, start: u128, duration: u128) -> f32 {
+    (curr.saturating_sub(start) as f32 / duration as f32).min(1.0)
+}
+
+// What gets written into the padding of every instance in the tetromino storage buffer
+#[derive(Clone, Copy, Default)]
+pub struct BlockAnimation {
+    // 0 draws the block as is, 1 draws it white
+    pub flash: f32,
+    // 0 is the whole block, 1 has shrunk away to nothing
+    pub dissolve: f32,
+    // in cells, negative is further up the screen
+    pub offset_y: f32,
+}
+
+// Animates blocks from the board events: a flash when a piece locks, the full rows
+// flashing then dissolving during the line clear delay, and the stack falling into the gap after
+pub struct Animator {
+    curr: u128,
+
+    lock_flashes: Vec<(Tetromino, u128)>,
+
+    clearing_rows: Vec<usize>,
+    clear_progress: f32,
+
+    collapse_shifts: [u8; PLAYFIELD_HEIGHT],
+    collapse_start: Option<u128>,
+
+    // line clears briefly show a hidden stack again
+    reveal_start: Option<u128>,
+}
+
+impl Animator {
+    pub fn new() -> Animator {
+        Animator {
+            curr: 0,
+            lock_flashes: Vec::new(),
+            clearing_rows: Vec::new(),
+            clear_progress: 0.0,
+            collapse_shifts: [0; PLAYFIELD_HEIGHT],
+            collapse_start: None,
+            reveal_start: None,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &BoardEvent, curr: u128) {
+        match event {
+            BoardEvent::PieceLocked(tetromino) => self.lock_flashes.push((*tetromino, curr)),
+
+            BoardEvent::LinesCleared { rows, .. } => {
+                let rows: Vec<usize> = rows.iter().map(|row| row.y).collect();
+
+                self.collapse_shifts = Animator::get_collapse_shifts(&rows);
+                self.collapse_start = Some(curr);
+                self.reveal_start = Some(curr);
+            }
+
+            BoardEvent::GameReset => *self = Animator::new(),
+
+            _ => (),
+        }
+    }
+
+    // how many rows every row that survived the clear fell, indexed by where it ended up
+    fn get_collapse_shifts(rows: &[usize]) -> [u8; PLAYFIELD_HEIGHT] {
+        let mut shifts = [0u8; PLAYFIELD_HEIGHT];
+
+        for y in 0..PLAYFIELD_HEIGHT {
+            if rows.contains(&y) {
+                continue;
+            }
+
+            let shift = rows.iter().filter(|row| **row > y).count();
+
+            if y + shift < PLAYFIELD_HEIGHT {
+                shifts[y + shift] = shift as u8;
+            }
+        }
+
+        shifts
+    }
+
+    // the clearing rows come straight from the playfield so they stop along with it when paused
+    pub fn update(&mut self, curr: u128, clearing_rows: &[usize], clear_progress: f32) {
+        self.curr = curr;
+
+        self.clearing_rows.clear();
+        self.clearing_rows.extend_from_slice(clearing_rows);
+        self.clear_progress = clear_progress;
+
+        self.lock_flashes.retain(|(_, start)| curr.saturating_sub(*start) < LOCK_FLASH_TIME);
+
+        if self.collapse_start.is_some_and(|start| curr.saturating_sub(start) >= COLLAPSE_TIME) {
+            self.collapse_start = None;
+        }
+
+        if self.reveal_start.is_some_and(|start| curr.saturating_sub(start) >= REVEAL_TIME) {
+            self.reveal_start = None;
+        }
+    }
+
+    // how much of a hidden stack is showing after a line clear, fading back out towards the end
+    pub fn get_reveal(&self) -> f32 {
+        match self.reveal_start {
+            Some(start) => 1.0 - ease_in_quad(get_progress(self.curr, start, REVEAL_TIME)),
+            None => 0.0,
+        }
+    }
+
+    pub fn get_block_animation(&self, x: usize, y: usize) -> BlockAnimation {
+        let mut animation = BlockAnimation::default();
+
+        for (tetromino, start) in &self.lock_flashes {
+            if tetromino.get_data().chunks(2).any(|pos| pos[0] as usize == x && pos[1] as usize == y) {
+                animation.flash = animation.flash.max(1.0 - get_progress(self.curr, *start, LOCK_FLASH_TIME));
+            }
+        }
+
+        // flash for the first half of the delay, dissolve over the second
+        if self.clearing_rows.contains(&y) {
+            animation.flash = animation.flash.max(1.0 - self.clear_progress * 2.0);
+            animation.dissolve = ease_in_quad(((self.clear_progress - 0.5) * 2.0).clamp(0.0, 1.0));
+        }
+
+        if let Some(start) = self.collapse_start {
+            let t = ease_out_cubic(get_progress(self.curr, start, COLLAPSE_TIME));
+            animation.offset_y = -(self.collapse_shifts[y] as f32) * (1.0 - t);
+        }
+
+        animation
+    }
+}
@@ -9,68 +9,21 @@ use std::{
 };
 
 use super::*;
-use crate::{vulkan::{core::*, *}, types::*};
+use crate::{vulkan::{core::*, *}, types::*, canvas::*};
 
 use bytemuck::bytes_of;
 
-use rand::prelude::*;
+use crate::game::bot::{Bot, Controller, TbpBot};
+use crate::game::input::{Action, InputConfig, InputRecorder, InputScript, InputSource};
+use crate::game::particles::ParticleSystem;
+use crate::game::settings::Accessibility;
+use crate::game::stats::Stats;
 
-
-#[derive(Clone, Copy)]
-pub enum GameState{
-    RUNNING,
-    END
-}
-
-#[inline]
-fn lerp(x: f32, y: f32, a: f32) -> f32{
-    x * (1.0 - a) + y * a
-}
-
-#[inline]
-fn length(x: f32, y: f32) -> f32{
-    (x.powf(2.0) + y.powf(2.0)).sqrt()
-}
-
-struct ScreenShake {
-    points: [(f32, f32); 12],
-    last_dir_change: u64,
-    curr_point_index: usize,
-}
-
-impl ScreenShake {
-    pub fn new() -> ScreenShake {
-        let mut rng = rand::rng();
-
-        let mut points = [(0f32, 0f32); 12];
-
-        for p in points.iter_mut() {
-            *p = (rng.random_range(-10..10) as f32,
-            rng.random_range(-10..10) as f32);
-        }
-
-        ScreenShake { points, last_dir_change: 0, curr_point_index: 0 }
-    }
-
-    pub fn update(&mut self, curr_time: u64) -> (f32, f32) {
-
-        if curr_time - self.last_dir_change > 20 {
-            self.last_dir_change = curr_time;
-            self.curr_point_index += 1;
-        }
-
-        if self.curr_point_index >= self.points.len() {
-            return (std::f32::NAN, std::f32::NAN);
-        }
-        
-        (self.points[self.curr_point_index].0, self.points[self.curr_point_index].1)
-    }
-}
+use rand::{prelude::*, rngs::StdRng};
 
 
 pub struct Board {
-    tetromino: Tetromino,
-    grid: Grid,
+    playfield: Playfield,
 
     previous_tetromino_count: usize,
 
@@ -85,20 +38,27 @@ pub struct Board {
 
     transfer_finished_fence: Fence,
 
-    fall_interval: u32,
-    previous_interval: u128,
+    tetromino_instance_count: u32,
 
-    score: Arc<Mutex<u32>>,
 
-    game_state: GameState,
+    screen_shake: Option<ScreenShake>,
 
-    tetromino_instance_count: u32,
+    stats: Stats,
+    mode: GameMode,
+    timing: Timing,
 
-    rng: ThreadRng,
+    animator: Animator,
+    particles: Option<ParticleSystem>,
 
-    place_sound: Sound,
+    input_config: InputConfig,
+    // played back on top of the player's own actions
+    input_source: Option<Box<dyn InputSource>>,
+    recorder: Option<InputRecorder>,
+    // game time when the current game started, scripts count from here
+    game_start: u128,
 
-    screen_shake: Option<ScreenShake>
+    // set whenever something a spectator would see changes, cleared once it has been published
+    changed: bool,
 }
 
 impl<'a> Board {
@@ -108,22 +68,28 @@ impl<'a> Board {
     ) -> Board {
         let mut transfer_command_buffer = CommandBuffer::new(device, command_pool, false);
 
+        let skin = Skin::from_config("conf/skin.json");
+
         let buffers = Board::initialize_buffers(
             device,
             &mut transfer_command_buffer,
-            "tetromino_piece.png",
+            &skin.texture,
         );
 
-        let place_sound = Sound::new("place.wav",
-        2.0, false);
+        let bot: Box<dyn Controller> = match TbpBot::from_config("conf/tbp.json") {
+            Some(tbp_bot) => Box::new(tbp_bot),
+            None => Box::new(Bot::new("conf/bot.json")),
+        };
 
-        let mut rng = rand::rng();
+        let timing = Timing::load("conf/timing.json");
+        let accessibility = Accessibility::load("conf/accessibility.json");
 
-        let mut tetromino = Tetromino::new((3, 2), [255; 3], TetrominoShape::rand(&mut rng, TetrominoShape::I));
-        tetromino.translate((0, 0), &[[[0; 4]; PLAYFIELD_WIDTH]; PLAYFIELD_HEIGHT]);
+        // nothing is played before start_game, which picks each game's seed
+        let mut playfield = Playfield::new(StdRng::seed_from_u64(0), skin.palette, bot);
+        playfield.set_delays(timing.entry_delay, timing.line_clear_delay);
 
         Board {
-            tetromino,
+            playfield,
             transfer_command_buffer: transfer_command_buffer,
             instance_buffer: None,
             previous_tetromino_count: 0,
@@ -132,20 +98,23 @@ impl<'a> Board {
             projection_uniform: buffers.2,
             tetromino_tex: buffers.3,
             transfer_finished_fence: Fence::new(device, false),
-            grid: [[[0; 4]; PLAYFIELD_WIDTH]; PLAYFIELD_HEIGHT],
-            fall_interval: 1500,
-            previous_interval: 0,
             tetromino_instance_count: 0,
-            rng,
-            game_state: GameState::RUNNING,
-            score: Arc::new(Mutex::new(0)),
-            place_sound,
-            screen_shake: None
+            screen_shake: ScreenShake::from_config("conf/screen_shake.json", &accessibility),
+            stats: Stats::new(),
+            mode: GameMode::Marathon,
+            timing,
+            animator: Animator::new(),
+            particles: ParticleSystem::from_config(device, command_pool, "conf/particles.json"),
+            input_config: InputConfig::load("conf/input.json"),
+            input_source: None,
+            recorder: None,
+            game_start: 0,
+            changed: true,
         }
     }
 
     fn get_projection_matrix(offset: (f32, f32)) -> [f32; 16] {
-        let screen_res = (720, 1280);
+        let screen_res = (CANVAS_WIDTH, CANVAS_HEIGHT);
 
         let left = 0f32 + offset.0;
         let right = screen_res.0 as f32 + offset.0;
@@ -302,86 +271,96 @@ impl<'a> Board {
         }
     }
 
-    fn get_instance_data(&mut self) -> Vec<u8> {
-        let mut data = Vec::<u8>::with_capacity(PLAYFIELD_HEIGHT * PLAYFIELD_WIDTH * 20);
+    // matches InstanceDat in the tetromino shaders, 48 bytes with std430 padding
+    fn push_instance(data: &mut Vec<u8>, color: [u8; 4], pos: (u32, u32), animation: BlockAnimation) {
+        data.extend_from_slice(&[
+            (color[0] as u32).to_ne_bytes(),
+            (color[1] as u32).to_ne_bytes(),
+            (color[2] as u32).to_ne_bytes(),
+            (color[3] as u32).to_ne_bytes(),
+            pos.0.to_ne_bytes(),
+            pos.1.to_ne_bytes(),
+            animation.flash.to_ne_bytes(),
+            animation.dissolve.to_ne_bytes(),
+            animation.offset_y.to_ne_bytes(),
+        ].concat());
+
+        data.extend_from_slice(&[0u8; 12]);
+    }
 
-        for y in 0..self.grid.len() {
-            for x in 0..self.grid[y].len() {
-                if self.grid[y][x] == [0; 4] {
-                    continue;
-                }
+    // a block covers scale by scale cells on screen
+    fn push_block(data: &mut Vec<u8>, color: [u8; 4], pos: (u32, u32), animation: BlockAnimation, scale: u32) {
+        let animation = BlockAnimation { offset_y: animation.offset_y * scale as f32, ..animation };
 
-                data.extend_from_slice(&[
-                    (self.grid[y][x][0] as u32).to_ne_bytes(),
-                    (self.grid[y][x][1] as u32).to_ne_bytes(),
-                    (self.grid[y][x][2] as u32).to_ne_bytes(),
-                    (self.grid[y][x][3] as u32).to_ne_bytes(),
-                    (x as u32).to_ne_bytes(),
-                    (y as u32).to_ne_bytes(),
-                    0u32.to_ne_bytes(),
-                    0u32.to_ne_bytes(),
-                ].concat());
+        for dy in 0..scale {
+            for dx in 0..scale {
+                Board::push_instance(data, color, (pos.0 * scale + dx, pos.1 * scale + dy), animation);
             }
         }
+    }
+
+    fn get_instance_data(&mut self) -> Vec<u8> {
+        let mut data = Vec::<u8>::with_capacity(PLAYFIELD_HEIGHT * PLAYFIELD_WIDTH * 48);
 
-        for pos in self.tetromino.get_data().chunks(2) {
-            let color = self.tetromino.get_color();
+        let grid = self.playfield.get_grid();
+        let mut tetromino = *self.playfield.get_tetromino();
+        let scale = self.mode.get_block_scale();
 
-            data.extend_from_slice(&[
-                (color[0] as u32).to_ne_bytes(),
-                (color[1] as u32).to_ne_bytes(),
-                (color[2] as u32).to_ne_bytes(),
-                (color[3] as u32).to_ne_bytes(),
-            ].concat());
+        // the whole stack shows once the game is over
+        let reveal = match self.playfield.get_game_state() {
+            GameState::END => 1.0,
+            _ => self.animator.get_reveal(),
+        };
 
-            data.extend_from_slice(&[
-                (pos[0] as u32).to_ne_bytes(),
-                (pos[1] as u32).to_ne_bytes(),
-            ].concat());
+        for y in 0..grid.len() {
+            let is_clearing = self.playfield.get_clearing_rows().contains(&y);
 
-            data.extend_from_slice(&[0u8; 8]);
-        }
+            for x in 0..grid[y].len() {
+                if grid[y][x] == [0; 4] || grid[y][x] == WALL_BLOCK {
+                    continue;
+                }
 
-        for pos in self.tetromino.get_ghost_data(&self.grid).chunks(2) {
-            let color = self.tetromino.get_ghost_color();
+                let visibility = if is_clearing {
+                    1.0
+                } else {
+                    self.mode.get_block_visibility(self.playfield.get_block_age(x, y)).max(reveal)
+                };
 
-            data.extend_from_slice(&[
-                (color[0] as u32).to_ne_bytes(),
-                (color[1] as u32).to_ne_bytes(),
-                (color[2] as u32).to_ne_bytes(),
-                (color[3] as u32).to_ne_bytes(),
-            ].concat());
+                // hidden blocks are left out entirely so they don't light up their neighbours either
+                if visibility <= 0.0 {
+                    continue;
+                }
 
+                let color = grid[y][x].map(|channel| (channel as f32 * visibility) as u8);
 
-            data.extend_from_slice(&[
-                (pos[0] as u32).to_ne_bytes(),
-                (pos[1] as u32).to_ne_bytes(),
-            ].concat());
+                Board::push_block(&mut data, color, (x as u32, y as u32), self.animator.get_block_animation(x, y), scale);
+            }
+        }
 
-            data.extend_from_slice(&[0u8; 8]);
+        // the locked piece is already part of the grid during the line clear and entry delays
+        if self.playfield.is_piece_active() {
+            for pos in tetromino.get_data().chunks(2) {
+                Board::push_block(&mut data, tetromino.get_color(), (pos[0] as u32, pos[1] as u32), BlockAnimation::default(), scale);
+            }
+
+            for pos in tetromino.get_ghost_data(grid).chunks(2) {
+                Board::push_block(&mut data, tetromino.get_ghost_color(), (pos[0] as u32, pos[1] as u32), BlockAnimation::default(), scale);
+            }
         }
 
         data.extend_from_slice(&[0u8; 8]);
 
         data.shrink_to_fit();
 
-        self.tetromino_instance_count = (data.len() / 32) as u32;
+        self.tetromino_instance_count = (data.len() / 48) as u32;
 
         data
     }
 
     fn handle_screen_shake(&mut self, device: &Device, time: u64) {
-        if self.screen_shake.is_none() {
+        let Some(pos) = self.screen_shake.as_mut().and_then(|shake| shake.update(time)) else {
             return;
-        }
-
-        let mut pos = self.screen_shake.as_mut().unwrap().update(time);
-
-        if pos.0.is_nan() || pos.1.is_nan() {
-            self.screen_shake = None;
-            pos = (0.0, 0.0);
-        }
-
+        };
 
         let projection = Board::get_projection_matrix(pos);
 
@@ -439,171 +418,87 @@ impl<'a> Board {
         self.transfer_command_buffer.cleanup(device);
     }
 
-    fn check_collision_in_dir(&self, x: i8, y: i8) -> bool {
-        for pos in self.tetromino.get_data().chunks(2) {
-            let offset_x = ((pos[0] as i8) + x) as usize;
-            let offset_y = ((pos[1] as i8) + y) as usize;
+    fn get_curr() -> u128 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis()
+    }
 
-            if offset_x >= PLAYFIELD_WIDTH || offset_y >= PLAYFIELD_HEIGHT {
-                return false;
-            }
+    // adds whatever the input source has due and records the moves, nothing happens in game time while paused
+    fn handle_inputs(&mut self, actions: &[Action], curr: u128) -> Vec<Action> {
+        let mut actions = actions.to_vec();
 
-            if self.grid[offset_y][offset_x] != [0; 4] {
-                return true;
-            }
+        if !matches!(self.playfield.get_game_state(), GameState::RUNNING) {
+            return actions;
         }
 
-        false
-    }
+        let time = self.playfield.get_game_time(curr).saturating_sub(self.game_start);
 
-    fn get_random_color(&mut self) -> [u8; 3] {
-        match self.rng.random_range(1..7) {
-            1 => [255, 0, 0],
-            2 => [0, 0, 255],
-            3 => [0, 255, 0],
-            4 => [255, 255, 0],
-            5 => [255, 0, 255],
-            6 => [0, 255, 255],
-            _ => [255, 255, 255],
+        if let Some(input_source) = &mut self.input_source {
+            actions.extend(input_source.poll(time));
         }
-    }
-
-    fn handle_inputs(&mut self, events: &glfw::GlfwReceiver<(f64, glfw::WindowEvent)>, audio_manager: &mut AudioManager) {
-        for event in glfw::flush_messages(&events) {
-            match event.1 {
-                glfw::WindowEvent::Key(glfw::Key::R, _, glfw::Action::Press, _) => {
-                    self.tetromino.rotate(tetromino::Orientation::RIGHT, &self.grid)
-                }
-
-                glfw::WindowEvent::Key(glfw::Key::Left, _, glfw::Action::Press, _) => {
-                    self.tetromino.translate((-1, 0), &self.grid);
-                }
-
-                glfw::WindowEvent::Key(glfw::Key::Right, _, glfw::Action::Press, _) => {
-                    self.tetromino.translate((1, 0), &self.grid);
-                }
-
-                glfw::WindowEvent::Key(glfw::Key::Down, _, glfw::Action::Press, _) => {
-                    self.handle_block_collision(audio_manager);
-                    self.tetromino.translate((0, 1), &self.grid);
-
-                    self.previous_interval = SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap()
-                        .as_millis();
-                }
 
-                glfw::WindowEvent::Key(glfw::Key::Space, _, glfw::Action::Press, _) => {
-                    while self.tetromino.translate((0, 1), &self.grid) {}
-                    self.handle_block_collision(audio_manager);
-                },
-
-                glfw::WindowEvent::Key(glfw::Key::N, _, glfw::Action::Press, _) => {
-                    self.reset_game();
-                },
-
-                _ => (),
-            }
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(time, &actions);
         }
-    }
 
-    fn handle_gravity(&mut self) {
-        self.tetromino.translate((0, 1), &self.grid);
+        actions
     }
 
-    pub fn reset_game(&mut self) {
-        self.grid = [[[0; 4]; PLAYFIELD_WIDTH]; PLAYFIELD_HEIGHT];
-        self.game_state = GameState::RUNNING;
-        
-        self.add_tetromino(0, 0);
+    fn handle_events(&mut self, audio_manager: &mut AudioManager, curr: u128) {
+        let events = self.playfield.drain_events();
 
-    }
-
-    fn handle_block_collision(&mut self, audio_manager: &mut AudioManager) {
+        // locks, clears and resets are what change the grid and the score
+        self.changed |= !events.is_empty();
 
-        if self.tetromino.is_topped_out() {
-            self.game_state = GameState::END;
-            return;
-        }
+        for event in events {
+            self.stats.handle_event(&event);
+            self.animator.handle_event(&event, curr);
 
-        if !self.tetromino.is_grounded(&self.grid) {
-            self.game_state = GameState::RUNNING;
-            return;
-        }
-
-        for pos in self.tetromino.get_data().chunks(2) {
-            self.grid[pos[1] as usize][pos[0] as usize] = self.tetromino.get_color();
-        }
+            if let Some(particles) = &mut self.particles {
+                particles.handle_event(&event);
+            }
 
-        audio_manager.play(&mut self.place_sound);
-        
-        self.add_tetromino(2, 2);
+            if let Some(screen_shake) = &mut self.screen_shake {
+                screen_shake.handle_event(&event);
+            }
 
-        if !self.tetromino.is_valid(&self.grid) {
-            self.game_state = GameState::END;
+            audio_manager.handle_event(&event);
         }
     }
 
-    fn handle_line_clear(&mut self) {
-        let mut consecutive_clears = 0u8;
 
-        for y in 0..PLAYFIELD_HEIGHT {
-            let mut is_full = true;
+    pub fn update(&mut self, actions: &[Action], audio_manager: &mut AudioManager) {
+        let curr = Board::get_curr();
 
-            for x in 0..PLAYFIELD_WIDTH {
-                if self.grid[y][x] == [0; 4] {
-                    is_full = false;
-                }
-            }
-
-            if !is_full {
-                continue;
-            }
-
-            consecutive_clears += 1;
+        let actions = self.handle_inputs(actions, curr);
 
-            
-            for y_new in (0..y).rev() {
-                self.grid[y_new + 1] = self.grid[y_new];
-            }
-        }
+        // gravity and pausing don't raise events, they only show in the piece and the state
+        let previous = (self.playfield.get_tetromino().get_data(), self.playfield.get_game_state());
 
-        if consecutive_clears != 0 {
-            self.screen_shake = Some(ScreenShake::new());
-        }
+        self.playfield.update(&actions, curr);
+        self.handle_events(audio_manager, curr);
 
+        self.changed |= previous != (self.playfield.get_tetromino().get_data(), self.playfield.get_game_state());
 
-        *self.score.lock().expect("Failed to lock") +=  match consecutive_clears {
-            0 => 0,
-            1 => 100,
-            2 => 300,
-            3 => 500,
-            4 => 800,
-            _ => 2500
-        };
-    }
+        let stack_height = self.playfield.get_stack_height() as f32 / self.playfield.get_height() as f32;
+        audio_manager.update_music(stack_height, self.playfield.get_lines_cleared());
 
-    fn fixed_update(&mut self, audio_manager: &mut AudioManager) {
-        let curr = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis();
+        self.stats.update(curr, self.playfield.get_game_state());
 
-        if (self.previous_interval + self.fall_interval as u128) >= curr {
-            return;
+        // the recording is finished with the stats once the game is over, either way it ends
+        if matches!(self.playfield.get_game_state(), GameState::END) || self.is_mode_complete() {
+            if let Some(recorder) = self.recorder.take() {
+                recorder.finish(&self.stats);
+            }
         }
 
-        self.previous_interval = curr;
-
-        self.handle_block_collision(audio_manager);
-        self.handle_gravity();
-
-    }
+        self.animator.update(curr, self.playfield.get_clearing_rows(), self.playfield.get_phase_progress());
 
-    pub fn update(&mut self, events: &glfw::GlfwReceiver<(f64, glfw::WindowEvent)>, audio_manager: &mut AudioManager) {
-        self.fixed_update(audio_manager);
-        self.handle_inputs(events, audio_manager);
-        self.handle_line_clear();
+        if let Some(particles) = &mut self.particles {
+            particles.update(curr, self.playfield.get_game_state());
+        }
     }
 
     pub fn draw(
@@ -628,31 +523,93 @@ impl<'a> Board {
         );
     }
 
-    pub fn add_tetromino(&mut self, x: i8, y: i8) {
-        self.tetromino = Tetromino::new(
-            (x, y),
-            self.get_random_color(),
-            TetrominoShape::rand(&mut self.rng, self.tetromino.get_shape()),
-        );
+    pub fn draw_particles(&mut self, device: &Device, render_pass: &RenderPass, command_buffer: &CommandBuffer, subpass_index: u32) {
+        if let Some(particles) = &mut self.particles {
+            particles.draw(device, render_pass, command_buffer, &self.vertex_buffer, &self.index_buffer, subpass_index);
+        }
+    }
+
+    // a fresh game that stays paused until it's resumed, so a countdown can run over it
+    pub fn start_game(&mut self, mode: GameMode) {
+        let curr = Board::get_curr();
+
+        self.mode = mode;
+        self.playfield.set_big(mode.get_block_scale() > 1);
+
+        // a replay deals the pieces it was recorded with, and a recording notes its seed so it can be replayed
+        let script = self.input_config.replay.as_deref().map(InputScript::load);
+        let seed = script.as_ref().and_then(InputScript::get_seed).unwrap_or_else(rand::random);
+        self.playfield.set_seed(seed);
+
+        self.playfield.resume(curr);
+        self.playfield.reset_game();
+        self.playfield.drain_events();
+        self.playfield.pause(curr);
 
-        let mut scalar = 0;
+        self.game_start = self.playfield.get_game_time(curr);
+        self.changed = true;
 
+        // only the latest game is kept in the recording
+        self.input_source = script.map(|script| Box::new(script) as Box<dyn InputSource>);
+        self.recorder = self.input_config.record.as_deref().map(|path| InputRecorder::create(path, mode.get_name(), seed));
 
-        while !self.tetromino.is_in_bounds() {
-            self.tetromino.translate((scalar, scalar), &self.grid);
-            scalar += 1;
+        self.stats = Stats::new();
+        self.stats.update(curr, GameState::PAUSED);
+        self.animator = Animator::new();
+
+        if let Some(particles) = &mut self.particles {
+            particles.handle_event(&BoardEvent::GameReset);
+            particles.set_scale(mode.get_block_scale() as f32);
         }
+    }
+
+    pub fn pause_game(&mut self) {
+        self.playfield.pause(Board::get_curr());
+    }
+
+    pub fn resume_game(&mut self) {
+        self.playfield.resume(Board::get_curr());
+    }
+
+    // the mode's goal was reached, as opposed to topping out
+    pub fn is_mode_complete(&self) -> bool {
+        self.mode.is_complete(self.playfield.get_lines_cleared(), self.stats.get_time_played())
+    }
 
-        while self.tetromino.translate((0, -1), &self.grid){}
-  
+    pub fn get_mode(&self) -> GameMode {
+        self.mode
+    }
+
+    pub fn get_timing(&self) -> Timing {
+        self.timing
     }
 
     pub fn get_tetromino_tex(&self) -> &Texture {
         &self.tetromino_tex
     }
 
+    pub fn get_grid(&self) -> &Grid {
+        self.playfield.get_grid()
+    }
+
+    pub fn get_tetromino(&self) -> &Tetromino {
+        self.playfield.get_tetromino()
+    }
+
     pub fn get_score(&self) -> Arc<Mutex<u32>> {
-        self.score.clone()
+        self.playfield.get_score()
+    }
+
+    pub fn get_lines_cleared(&self) -> u32 {
+        self.playfield.get_lines_cleared()
+    }
+
+    pub fn get_finesse_faults(&self) -> u32 {
+        self.playfield.get_finesse_faults()
+    }
+
+    pub fn get_stats(&self) -> &Stats {
+        &self.stats
     }
 
     pub fn get_tetromino_instance_count(&self) -> u32 {
@@ -734,8 +691,12 @@ impl<'a> Board {
         ([buffer_write_set_instance_dat], descriptor_infos)
     }
 
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
     pub fn get_game_state(&self) -> GameState {
-        self.game_state
+        self.playfield.get_game_state()
     }
 
     pub fn get_required_vertex_input_states() -> ([vk::PipelineVertexInputStateCreateInfo<'a>; 1], VertexInputData){
@@ -781,5 +742,9 @@ impl<'a> Board {
         self.vertex_buffer.destroy(device);
         self.instance_buffer.as_mut().unwrap().destroy(device);
         self.projection_uniform.destroy(device);
+
+        if let Some(particles) = &mut self.particles {
+            particles.destroy(device);
+        }
     }
 }
@@ -0,0 +1,119 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::*;
+use crate::game::input::Action;
+use crate::types::*;
+
+type Footprint = [(u8, u8); 4];
+
+const SHAPES: [TetrominoShape; 7] = [
+    TetrominoShape::I,
+    TetrominoShape::J,
+    TetrominoShape::L,
+    TetrominoShape::O,
+    TetrominoShape::S,
+    TetrominoShape::T,
+    TetrominoShape::Z,
+];
+
+// The columns and rotation a piece ended up in, regardless of how tall the stack under it was
+fn get_footprint(tetromino: &Tetromino) -> Footprint {
+    let data = tetromino.get_data();
+    let min_y = data.chunks(2).map(|pos| pos[1]).min().unwrap_or(0);
+
+    let mut footprint = [(0u8, 0u8); 4];
+    for (i, pos) in data.chunks(2).enumerate() {
+        footprint[i] = (pos[0], pos[1] - min_y);
+    }
+
+    footprint.sort_unstable();
+
+    footprint
+}
+
+// Whether the piece could have been hard dropped straight into place, the grid already has it locked in
+fn is_dropped(tetromino: &Tetromino, grid: &Grid) -> bool {
+    let data = tetromino.get_data();
+    let cells: Vec<(u8, u8)> = data.chunks(2).map(|pos| (pos[0], pos[1])).collect();
+
+    cells.iter().all(|&(x, y)| {
+        (0..y).all(|above| grid[above as usize][x as usize] == [0; 4] || cells.contains(&(x, above)))
+    })
+}
+
+// Breadth first search over moves and rotations on an empty grid, so every footprint maps to the fewest presses that reach it
+fn compute_minimal_inputs(spawn: &Tetromino) -> HashMap<Footprint, u32> {
+    let grid: Grid = [[[0; 4]; PLAYFIELD_WIDTH]; PLAYFIELD_HEIGHT];
+
+    let mut minimal_inputs = HashMap::<Footprint, u32>::new();
+    let mut visited = HashSet::<((i8, i8), Orientation)>::new();
+    let mut queue = VecDeque::<(Tetromino, u32)>::new();
+
+    visited.insert((spawn.get_pos(), spawn.get_orientation()));
+    queue.push_back((*spawn, 0));
+
+    while let Some((curr, inputs)) = queue.pop_front() {
+        let mut dropped = curr;
+        while dropped.translate((0, 1), &grid) {}
+
+        minimal_inputs.entry(get_footprint(&dropped)).or_insert(inputs);
+
+        for action in [Action::MoveLeft, Action::MoveRight, Action::RotateCW] {
+            let mut next = curr;
+
+            match action {
+                Action::MoveLeft => {
+                    next.translate((-1, 0), &grid);
+                }
+                Action::MoveRight => {
+                    next.translate((1, 0), &grid);
+                }
+                _ => next.rotate(Orientation::RIGHT, &grid),
+            }
+
+            if visited.insert((next.get_pos(), next.get_orientation())) {
+                queue.push_back((next, inputs + 1));
+            }
+        }
+    }
+
+    minimal_inputs
+}
+
+// Fewest moves and rotations needed for every column and rotation of a piece, per shape and spawn position.
+// The regular spawns are worked out up front, anything else (like the first piece after a reset) on first use.
+pub struct FinesseTable {
+    entries: HashMap<(TetrominoShape, (i8, i8), Orientation), HashMap<Footprint, u32>>,
+}
+
+impl FinesseTable {
+    pub fn new() -> FinesseTable {
+        let grid: Grid = [[[0; 4]; PLAYFIELD_WIDTH]; PLAYFIELD_HEIGHT];
+
+        let mut table = FinesseTable { entries: HashMap::new() };
+
+        for shape in SHAPES {
+            let spawn = Tetromino::spawn((2, 2), [255; 3], shape, &grid);
+            table.get_entry(&spawn);
+        }
+
+        table
+    }
+
+    fn get_entry(&mut self, spawn: &Tetromino) -> &HashMap<Footprint, u32> {
+        self.entries
+            .entry((spawn.get_shape(), spawn.get_pos(), spawn.get_orientation()))
+            .or_insert_with(|| compute_minimal_inputs(spawn))
+    }
+
+    // None when the piece landed somewhere moves and rotations alone can't reach, like a soft dropped tuck
+    pub fn count_faults(&mut self, spawn: &Tetromino, placed: &Tetromino, grid: &Grid, inputs: u32) -> Option<u32> {
+        if !is_dropped(placed, grid) {
+            return None;
+        }
+
+        let minimal_inputs = *self.get_entry(spawn).get(&get_footprint(placed))?;
+
+        Some(inputs.saturating_sub(minimal_inputs))
+    }
+}
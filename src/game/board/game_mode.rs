@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+// What ends a game besides topping out, and how much of the stack stays on screen
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum GameMode {
+    #[default]
+    Marathon,
+    Sprint,
+    Ultra,
+    Fading,
+    Invisible,
+    Big,
+}
+
+const SPRINT_LINES: u32 = 40;
+const ULTRA_TIME: u64 = 120_000;
+
+// in milliseconds since the block locked, how long it stays and then how long it takes to fade away
+const FADING_DELAY: u128 = 3000;
+const FADING_TIME: u128 = 1000;
+const INVISIBLE_TIME: u128 = 150;
+
+impl GameMode {
+    pub const ALL: [GameMode; 6] = [GameMode::Marathon, GameMode::Sprint, GameMode::Ultra, GameMode::Fading, GameMode::Invisible, GameMode::Big];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            GameMode::Marathon => "MARATHON",
+            GameMode::Sprint => "SPRINT",
+            GameMode::Ultra => "ULTRA",
+            GameMode::Fading => "FADING",
+            GameMode::Invisible => "INVISIBLE",
+            GameMode::Big => "BIG",
+        }
+    }
+
+    // the name as shown on screen, in any case
+    pub fn from_name(name: &str) -> Option<GameMode> {
+        GameMode::ALL.into_iter().find(|mode| mode.get_name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn is_complete(&self, lines: u32, time_played: u64) -> bool {
+        match self {
+            GameMode::Marathon | GameMode::Fading | GameMode::Invisible | GameMode::Big => false,
+            GameMode::Sprint => lines >= SPRINT_LINES,
+            GameMode::Ultra => time_played >= ULTRA_TIME,
+        }
+    }
+
+    // how many cells across every block is drawn
+    pub fn get_block_scale(&self) -> u32 {
+        match self {
+            GameMode::Big => 2,
+            _ => 1,
+        }
+    }
+
+    // 1 for a block that's fully showing, 0 once it's gone
+    pub fn get_block_visibility(&self, age: u128) -> f32 {
+        let (delay, duration) = match self {
+            GameMode::Fading => (FADING_DELAY, FADING_TIME),
+            GameMode::Invisible => (0, INVISIBLE_TIME),
+            _ => return 1.0,
+        };
+
+        1.0 - (age.saturating_sub(delay) as f32 / duration as f32).min(1.0)
+    }
+}
@@ -1,7 +1,30 @@
+mod animation;
+use animation::{Animator, BlockAnimation};
+
 mod board;
 pub use board::Board;
-pub use board::GameState;
+
+mod finesse;
+
+mod game_mode;
+pub use game_mode::GameMode;
+
+mod screen_shake;
+use screen_shake::ScreenShake;
+
+mod playfield;
+pub use playfield::BoardEvent;
+pub use playfield::ClearedRow;
+pub use playfield::GameState;
+pub use playfield::Playfield;
+
+mod skin;
+pub use skin::{Palette, Skin};
+
+mod timing;
+pub use timing::Timing;
 
 mod tetromino;
 pub use tetromino::Tetromino;
-pub use tetromino::TetrominoShape;
\ No newline at end of file
+pub use tetromino::TetrominoShape;
+pub use tetromino::Orientation;
\ No newline at end of file
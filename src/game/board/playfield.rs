@@ -0,0 +1,670 @@
+use rand::{rngs::StdRng, SeedableRng};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+
+use super::*;
+use super::finesse::FinesseTable;
+use crate::game::bot::Controller;
+use crate::game::input::Action;
+use crate::types::*;
+
+#[derive(Clone, Copy, PartialEq, Serialize)]
+pub enum GameState{
+    RUNNING,
+    PAUSED,
+    END
+}
+
+// A full row as it was right before it got taken off the grid
+#[derive(Clone, Debug)]
+pub struct ClearedRow {
+    pub y: usize,
+    pub blocks: [[u8; 4]; PLAYFIELD_WIDTH],
+}
+
+// What happened during an update, drained by whoever presents the playfield (sound, screen shake...)
+#[derive(Clone, Debug)]
+pub enum BoardEvent {
+    KeyPressed,
+    PieceMoved,
+    PieceRotated,
+    // a rotation that was blocked and left the piece as it was
+    RotationFailed,
+    SoftDropped,
+    HardDropped { tetromino: Tetromino, distance: u8 },
+    PieceLocked(Tetromino),
+    LinesCleared { rows: Vec<ClearedRow>, t_spin: bool },
+    FinesseFault(u32),
+    GameOver,
+    GameReset,
+}
+
+// What the playfield is waiting on between one piece locking and the next one spawning
+#[derive(Clone, Debug)]
+enum Phase {
+    Falling,
+    LineClear { rows: Vec<usize>, t_spin: bool, start: u128 },
+    Entry { start: u128 },
+}
+
+// The rules of the game without any rendering or audio, driven by actions and a clock in milliseconds
+pub struct Playfield {
+    tetromino: Tetromino,
+    grid: Grid,
+    // when every block on the grid locked, in game time
+    lock_times: [[u128; PLAYFIELD_WIDTH]; PLAYFIELD_HEIGHT],
+    time: u128,
+
+    fall_interval: u32,
+    previous_interval: u128,
+
+    phase: Phase,
+    phase_progress: f32,
+    entry_delay: u32,
+    line_clear_delay: u32,
+
+    score: Arc<Mutex<u32>>,
+    lines_cleared: u32,
+    pieces_placed: u32,
+
+    finesse_table: FinesseTable,
+    spawn_tetromino: Tetromino,
+    piece_inputs: Vec<Action>,
+    finesse_faults: u32,
+
+    // whether the piece got where it is by rotating, for spotting T-spins
+    last_move_rotation: bool,
+
+    game_state: GameState,
+
+    // time spent paused is taken off the clock so gravity and the bot pick up where they left off
+    paused_time: u128,
+    pause_start: u128,
+
+    rng: StdRng,
+    palette: Palette,
+
+    // pieces play on a field half as wide and high, to be drawn at twice the size
+    big: bool,
+
+    bot: Box<dyn Controller>,
+    autoplay: bool,
+    // every piece spawned since the playfield was made, unlike pieces_placed it isn't reset with the game
+    pieces_dealt: u64,
+
+    events: Vec<BoardEvent>,
+}
+
+impl Playfield {
+    pub fn new(mut rng: StdRng, palette: Palette, bot: Box<dyn Controller>) -> Playfield {
+        let shape = TetrominoShape::rand(&mut rng, TetrominoShape::I);
+        let mut tetromino = Tetromino::new((3, 2), palette.get_color(shape), shape);
+        tetromino.translate((0, 0), &[[[0; 4]; PLAYFIELD_WIDTH]; PLAYFIELD_HEIGHT]);
+
+        Playfield {
+            tetromino,
+            grid: [[[0; 4]; PLAYFIELD_WIDTH]; PLAYFIELD_HEIGHT],
+            lock_times: [[0; PLAYFIELD_WIDTH]; PLAYFIELD_HEIGHT],
+            time: 0,
+            fall_interval: 1500,
+            previous_interval: 0,
+            phase: Phase::Falling,
+            phase_progress: 0.0,
+            entry_delay: 0,
+            line_clear_delay: 0,
+            score: Arc::new(Mutex::new(0)),
+            lines_cleared: 0,
+            pieces_placed: 0,
+            finesse_table: FinesseTable::new(),
+            spawn_tetromino: tetromino,
+            piece_inputs: Vec::new(),
+            finesse_faults: 0,
+            last_move_rotation: false,
+            game_state: GameState::RUNNING,
+            paused_time: 0,
+            pause_start: 0,
+            rng,
+            palette,
+            big: false,
+            bot,
+            autoplay: false,
+            pieces_dealt: 0,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn handle_action(&mut self, action: Action, curr: u128) {
+        if matches!(self.game_state, GameState::PAUSED) {
+            return;
+        }
+
+        // there's nothing to move until the next piece is in, or once the last one has topped out
+        if (!self.is_piece_active() || matches!(self.game_state, GameState::END)) && action.is_piece_move() {
+            return;
+        }
+
+        self.piece_inputs.push(action);
+
+        if matches!(self.game_state, GameState::RUNNING) && action.is_piece_move() {
+            self.events.push(BoardEvent::KeyPressed);
+        }
+
+        match action {
+            Action::RotateCW => {
+                let previous_orientation = self.tetromino.get_orientation();
+                self.tetromino.rotate(Orientation::RIGHT, &self.grid);
+
+                if self.tetromino.get_orientation() != previous_orientation {
+                    self.last_move_rotation = true;
+                    self.events.push(BoardEvent::PieceRotated);
+                } else {
+                    self.events.push(BoardEvent::RotationFailed);
+                }
+            }
+
+            Action::MoveLeft => {
+                if self.tetromino.translate((-1, 0), &self.grid) {
+                    self.last_move_rotation = false;
+                    self.events.push(BoardEvent::PieceMoved);
+                }
+            }
+
+            Action::MoveRight => {
+                if self.tetromino.translate((1, 0), &self.grid) {
+                    self.last_move_rotation = false;
+                    self.events.push(BoardEvent::PieceMoved);
+                }
+            }
+
+            Action::SoftDrop => {
+                self.handle_block_collision(curr);
+
+                if self.tetromino.translate((0, 1), &self.grid) {
+                    self.last_move_rotation = false;
+                    self.events.push(BoardEvent::SoftDropped);
+                }
+
+                self.previous_interval = curr;
+            }
+
+            Action::HardDrop => {
+                let mut distance = 0u8;
+
+                while self.tetromino.translate((0, 1), &self.grid) {
+                    distance += 1;
+                    self.last_move_rotation = false;
+                }
+
+                self.events.push(BoardEvent::HardDropped { tetromino: self.tetromino, distance });
+                self.handle_block_collision(curr);
+            },
+
+            Action::Restart => {
+                self.reset_game();
+            },
+
+            Action::ToggleBot => {
+                self.autoplay = !self.autoplay;
+            },
+
+            _ => (),
+        }
+    }
+
+    // the bot takes the same actions a player would, one per input interval
+    fn handle_autoplay(&mut self, curr: u128) {
+        if !self.autoplay || !matches!(self.game_state, GameState::RUNNING) || !self.is_piece_active() {
+            return;
+        }
+
+        if let Some(action) = self.bot.update(&self.tetromino, self.pieces_dealt, &self.grid, curr) {
+            self.handle_action(action, curr);
+        }
+    }
+
+    fn handle_gravity(&mut self) {
+        if self.tetromino.translate((0, 1), &self.grid) {
+            self.last_move_rotation = false;
+        }
+    }
+
+    // a T rotated into place with at least three of the corners around its centre filled, walls count as filled
+    fn is_t_spin(&self) -> bool {
+        if self.tetromino.get_shape() != TetrominoShape::T || !self.last_move_rotation {
+            return false;
+        }
+
+        let (x, y) = self.tetromino.get_pos();
+
+        let filled_corners = [(-1, -1), (1, -1), (-1, 1), (1, 1)]
+            .iter()
+            .filter(|(dx, dy)| {
+                let (corner_x, corner_y) = (x + dx, y + dy);
+
+                corner_x < 0
+                    || corner_y < 0
+                    || corner_x >= PLAYFIELD_WIDTH as i8
+                    || corner_y >= PLAYFIELD_HEIGHT as i8
+                    || self.grid[corner_y as usize][corner_x as usize] != [0; 4]
+            })
+            .count();
+
+        filled_corners >= 3
+    }
+
+    pub fn reset_game(&mut self) {
+        self.grid = [[[0; 4]; PLAYFIELD_WIDTH]; PLAYFIELD_HEIGHT];
+        self.lock_times = [[0; PLAYFIELD_WIDTH]; PLAYFIELD_HEIGHT];
+
+        // the cells outside of a smaller field get walled off so pieces collide with them like with the stack
+        let (width, height) = (self.get_width(), self.get_height());
+
+        for (y, row) in self.grid.iter_mut().enumerate() {
+            for (x, block) in row.iter_mut().enumerate() {
+                if x >= width || y >= height {
+                    *block = WALL_BLOCK;
+                }
+            }
+        }
+
+        self.game_state = GameState::RUNNING;
+        self.lines_cleared = 0;
+        self.pieces_placed = 0;
+        self.finesse_faults = 0;
+        *self.score.lock().expect("Failed to lock") = 0;
+        self.phase = Phase::Falling;
+        self.phase_progress = 0.0;
+
+        // the first piece doesn't depend on the last game's, so a seed always deals the same pieces
+        self.add_tetromino(0, 0, TetrominoShape::I);
+
+        self.events.push(BoardEvent::GameReset);
+
+    }
+
+    // a topped out piece can keep colliding after the game is over, the event only goes out once
+    fn end_game(&mut self) {
+        if !matches!(self.game_state, GameState::END) {
+            self.events.push(BoardEvent::GameOver);
+        }
+
+        self.game_state = GameState::END;
+    }
+
+    fn handle_block_collision(&mut self, curr: u128) {
+
+        if self.tetromino.is_topped_out() {
+            self.end_game();
+            return;
+        }
+
+        if !self.tetromino.is_grounded(&self.grid) {
+            self.game_state = GameState::RUNNING;
+            return;
+        }
+
+        for pos in self.tetromino.get_data().chunks(2) {
+            self.grid[pos[1] as usize][pos[0] as usize] = self.tetromino.get_color();
+            self.lock_times[pos[1] as usize][pos[0] as usize] = curr;
+        }
+
+        self.pieces_placed += 1;
+        self.events.push(BoardEvent::PieceLocked(self.tetromino));
+
+        // the finesse table only knows the full size field
+        if !self.big {
+            self.handle_finesse();
+        }
+
+        let rows: Vec<usize> = (0..self.get_height())
+            .filter(|y| self.grid[*y].iter().all(|block| *block != [0; 4]))
+            .collect();
+
+        if rows.is_empty() {
+            self.begin_entry(curr);
+            return;
+        }
+
+        self.phase = Phase::LineClear { rows, t_spin: self.is_t_spin(), start: curr };
+        self.handle_phase(curr);
+    }
+
+    // the full rows stay on the grid until the delay is up so they can be animated away
+    fn handle_line_clear(&mut self, rows: &[usize], t_spin: bool) {
+        let mut consecutive_clears = 0u8;
+
+        let cleared_rows: Vec<ClearedRow> = rows
+            .iter()
+            .map(|y| ClearedRow { y: *y, blocks: self.grid[*y] })
+            .collect();
+
+        for y in rows.iter().copied() {
+            consecutive_clears += 1;
+
+            
+            for y_new in (0..y).rev() {
+                self.grid[y_new + 1] = self.grid[y_new];
+                self.lock_times[y_new + 1] = self.lock_times[y_new];
+            }
+        }
+
+        if consecutive_clears != 0 {
+            self.lines_cleared += consecutive_clears as u32;
+            self.events.push(BoardEvent::LinesCleared { rows: cleared_rows, t_spin });
+        }
+
+
+        *self.score.lock().expect("Failed to lock") +=  match consecutive_clears {
+            0 => 0,
+            1 => 100,
+            2 => 300,
+            3 => 500,
+            4 => 800,
+            _ => 2500
+        };
+    }
+
+    fn begin_entry(&mut self, curr: u128) {
+        self.phase = Phase::Entry { start: curr };
+        self.handle_phase(curr);
+    }
+
+    fn spawn_next(&mut self) {
+        self.phase = Phase::Falling;
+        self.phase_progress = 0.0;
+
+        self.add_tetromino(2, 2, self.tetromino.get_shape());
+
+        if !self.tetromino.is_valid(&self.grid) {
+            self.end_game();
+        }
+    }
+
+    // moves on from the line clear and entry delays once they've run out, with no delay they pass within the same update
+    fn handle_phase(&mut self, curr: u128) {
+        match self.phase.clone() {
+            Phase::Falling => (),
+
+            Phase::LineClear { rows, t_spin, start } => {
+                let elapsed = curr - start;
+                self.phase_progress = if self.line_clear_delay == 0 { 1.0 } else { elapsed as f32 / self.line_clear_delay as f32 };
+
+                if elapsed >= self.line_clear_delay as u128 {
+                    self.handle_line_clear(&rows, t_spin);
+                    self.begin_entry(curr);
+                }
+            }
+
+            Phase::Entry { start } => {
+                let elapsed = curr - start;
+                self.phase_progress = if self.entry_delay == 0 { 1.0 } else { elapsed as f32 / self.entry_delay as f32 };
+
+                if elapsed >= self.entry_delay as u128 {
+                    self.spawn_next();
+                }
+            }
+        }
+    }
+
+    fn fixed_update(&mut self, curr: u128) {
+        self.handle_phase(curr);
+
+        if (self.previous_interval + self.fall_interval as u128) >= curr {
+            return;
+        }
+
+        self.previous_interval = curr;
+
+        if !self.is_piece_active() || matches!(self.game_state, GameState::END) {
+            return;
+        }
+
+        self.handle_block_collision(curr);
+        self.handle_gravity();
+
+    }
+
+    pub fn pause(&mut self, curr: u128) {
+        if !matches!(self.game_state, GameState::RUNNING) {
+            return;
+        }
+
+        self.game_state = GameState::PAUSED;
+        self.pause_start = curr;
+    }
+
+    pub fn resume(&mut self, curr: u128) {
+        if !matches!(self.game_state, GameState::PAUSED) {
+            return;
+        }
+
+        self.game_state = GameState::RUNNING;
+        self.paused_time += curr - self.pause_start;
+    }
+
+    pub fn toggle_pause(&mut self, curr: u128) {
+        match self.game_state {
+            GameState::RUNNING => self.pause(curr),
+            GameState::PAUSED => self.resume(curr),
+            GameState::END => (),
+        }
+    }
+
+    pub fn update(&mut self, actions: &[Action], curr: u128) {
+        if actions.contains(&Action::Pause) {
+            self.toggle_pause(curr);
+        }
+
+        if matches!(self.game_state, GameState::PAUSED) {
+            return;
+        }
+
+        let curr = curr - self.paused_time;
+        self.time = curr;
+
+        self.fixed_update(curr);
+
+        for action in actions {
+            self.handle_action(*action, curr);
+        }
+
+        self.handle_autoplay(curr);
+    }
+
+    pub fn add_tetromino(&mut self, x: i8, y: i8, prev_shape: TetrominoShape) {
+        let shape = TetrominoShape::rand(&mut self.rng, prev_shape);
+
+        self.tetromino = Tetromino::spawn((x, y), self.palette.get_color(shape), shape, &self.grid);
+
+        self.spawn_tetromino = self.tetromino;
+        self.pieces_dealt += 1;
+        self.piece_inputs.clear();
+        self.last_move_rotation = false;
+    }
+
+    // compares the moves and rotations spent on the piece that just locked with the fewest that reach the same spot
+    fn handle_finesse(&mut self) {
+        let inputs = self
+            .piece_inputs
+            .iter()
+            .filter(|action| matches!(action, Action::MoveLeft | Action::MoveRight | Action::RotateCW))
+            .count() as u32;
+
+        let Some(faults) = self.finesse_table.count_faults(&self.spawn_tetromino, &self.tetromino, &self.grid, inputs) else {
+            return;
+        };
+
+        if faults != 0 {
+            self.finesse_faults += faults;
+            self.events.push(BoardEvent::FinesseFault(faults));
+        }
+    }
+
+    pub fn drain_events(&mut self) -> Vec<BoardEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn set_delays(&mut self, entry_delay: u32, line_clear_delay: u32) {
+        self.entry_delay = entry_delay;
+        self.line_clear_delay = line_clear_delay;
+    }
+
+    // takes effect when the next game starts
+    pub fn set_big(&mut self, big: bool) {
+        self.big = big;
+    }
+
+    // takes effect when the next game starts
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    pub fn get_width(&self) -> usize {
+        if self.big { PLAYFIELD_WIDTH / 2 } else { PLAYFIELD_WIDTH }
+    }
+
+    pub fn get_height(&self) -> usize {
+        if self.big { PLAYFIELD_HEIGHT / 2 } else { PLAYFIELD_HEIGHT }
+    }
+
+    pub fn set_autoplay(&mut self, autoplay: bool) {
+        self.autoplay = autoplay;
+    }
+
+    pub fn get_grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    // the clock the playfield runs on, which stands still while paused
+    pub fn get_game_time(&self, curr: u128) -> u128 {
+        match self.game_state {
+            GameState::PAUSED => self.pause_start - self.paused_time,
+            _ => curr - self.paused_time,
+        }
+    }
+
+    // how long the block at x, y has been on the stack, in game time so it stops while paused
+    pub fn get_block_age(&self, x: usize, y: usize) -> u128 {
+        self.time.saturating_sub(self.lock_times[y][x])
+    }
+
+    pub fn get_tetromino(&self) -> &Tetromino {
+        &self.tetromino
+    }
+
+    // false during the line clear and entry delays, when the last piece is already in the grid
+    pub fn is_piece_active(&self) -> bool {
+        matches!(self.phase, Phase::Falling)
+    }
+
+    // rows that are full and about to collapse, for animating them
+    pub fn get_clearing_rows(&self) -> &[usize] {
+        match &self.phase {
+            Phase::LineClear { rows, .. } => rows,
+            _ => &[],
+        }
+    }
+
+    // how far into the current delay the playfield is, from 0 to 1
+    pub fn get_phase_progress(&self) -> f32 {
+        self.phase_progress.min(1.0)
+    }
+
+    pub fn get_score(&self) -> Arc<Mutex<u32>> {
+        self.score.clone()
+    }
+
+    pub fn get_lines_cleared(&self) -> u32 {
+        self.lines_cleared
+    }
+
+    pub fn get_pieces_placed(&self) -> u32 {
+        self.pieces_placed
+    }
+
+    pub fn get_finesse_faults(&self) -> u32 {
+        self.finesse_faults
+    }
+
+    // rows from the floor up to the highest locked block
+    pub fn get_stack_height(&self) -> u32 {
+        match self.grid.iter().position(|row| row.iter().any(|block| *block != [0; 4] && *block != WALL_BLOCK)) {
+            Some(y) => (self.get_height() - y) as u32,
+            None => 0,
+        }
+    }
+
+    pub fn get_game_state(&self) -> GameState {
+        self.game_state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{GameState, Palette, Playfield};
+    use crate::game::bot::Bot;
+    use crate::game::input::{InputScript, InputSource};
+    use crate::types::{PLAYFIELD_HEIGHT, PLAYFIELD_WIDTH};
+
+    fn get_playfield() -> Playfield {
+        Playfield::new(StdRng::seed_from_u64(0), Palette::default(), Box::new(Bot::new("conf/bot.json")))
+    }
+
+    // steps the playfield one frame at a time the way tetris-sim does
+    fn play(playfield: &mut Playfield, script: &str, until: u128) {
+        let mut script = InputScript::parse(script);
+
+        for curr in (0..=until).step_by(16) {
+            playfield.update(&script.poll(curr), curr);
+        }
+    }
+
+    #[test]
+    fn scripted_moves_reach_the_wall() {
+        let mut playfield = get_playfield();
+
+        play(&mut playfield, "0 MoveLeft\n16 MoveLeft\n32 MoveLeft\n48 MoveLeft\n64 MoveLeft\n100 HardDrop", 200);
+
+        assert_eq!(playfield.get_pieces_placed(), 1);
+        assert!(playfield.get_grid().iter().any(|row| row[0] != [0; 4]));
+        assert!(matches!(playfield.get_game_state(), GameState::RUNNING));
+    }
+
+    #[test]
+    fn a_scripted_drop_clears_the_row_it_completes() {
+        let mut playfield = get_playfield();
+
+        // fill the bottom row around wherever the first piece is going to land
+        let mut landing = *playfield.get_tetromino();
+        while landing.translate((0, 1), playfield.get_grid()) {}
+
+        let bottom = PLAYFIELD_HEIGHT - 1;
+        let cells: Vec<(usize, usize)> = landing.get_data().chunks(2).map(|pos| (pos[0] as usize, pos[1] as usize)).collect();
+
+        for x in (0..PLAYFIELD_WIDTH).filter(|x| !cells.contains(&(*x, bottom))) {
+            playfield.grid[bottom][x] = [255; 4];
+        }
+
+        play(&mut playfield, "0 HardDrop", 100);
+
+        assert_eq!(playfield.get_pieces_placed(), 1);
+        assert_eq!(playfield.get_lines_cleared(), 1);
+        assert_eq!(*playfield.get_score().lock().unwrap(), 100);
+        assert!(matches!(playfield.get_game_state(), GameState::RUNNING));
+    }
+
+    #[test]
+    fn dropping_in_one_place_tops_out() {
+        let mut playfield = get_playfield();
+
+        let script: String = (0..100).map(|i| format!("{} HardDrop\n", i * 20)).collect();
+        play(&mut playfield, &script, 2000);
+
+        // the drops left over once the stack reaches the top don't place anything
+        assert!(playfield.get_pieces_placed() < 100);
+        assert_eq!(playfield.get_lines_cleared(), 0);
+        assert!(matches!(playfield.get_game_state(), GameState::END));
+    }
+}
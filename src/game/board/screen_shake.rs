@@ -0,0 +1,175 @@
+use rand::prelude::*;
+use serde::Deserialize;
+use std::fs;
+
+use super::*;
+use crate::game::settings::Accessibility;
+use crate::types::*;
+
+#[inline]
+fn lerp(x: f32, y: f32, a: f32) -> f32 {
+    x * (1.0 - a) + y * a
+}
+
+// Amplitude in pixels and duration in milliseconds
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct Shake {
+    pub amplitude: f32,
+    pub duration: u32,
+}
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    pub enabled: bool,
+    // how long the view takes to swing to the next random point
+    pub interval: u32,
+
+    pub single: Shake,
+    pub double: Shake,
+    pub triple: Shake,
+    pub tetris: Shake,
+    pub t_spin_scale: f32,
+
+    // for a drop from the very top of the board, shorter drops shake less
+    pub hard_drop: Shake,
+}
+
+// Offsets the view after clears and hard drops, dying down over the duration of the strongest shake still going
+pub struct ScreenShake {
+    config: Config,
+    intensity: f32,
+
+    rng: ThreadRng,
+
+    shake: Option<Shake>,
+    // the shake starts on the next update since that's when its clock is known
+    start: Option<u64>,
+
+    previous_point: (f32, f32),
+    next_point: (f32, f32),
+    last_dir_change: u64,
+    previous_update: u64,
+}
+
+impl ScreenShake {
+    fn load_config(path: &str) -> Config {
+        let contents = fs::read_to_string(path).expect("Failed to read the screen shake config file");
+
+        serde_json::from_str(&contents).expect("Could not parse screen shake JSON config")
+    }
+
+    pub fn from_config(path: &str, accessibility: &Accessibility) -> Option<ScreenShake> {
+        let config = ScreenShake::load_config(path);
+
+        if !config.enabled || !accessibility.screen_shake || accessibility.shake_intensity <= 0.0 {
+            return None;
+        }
+
+        Some(ScreenShake {
+            config,
+            intensity: accessibility.shake_intensity,
+            rng: rand::rng(),
+            shake: None,
+            start: None,
+            previous_point: (0.0, 0.0),
+            next_point: (0.0, 0.0),
+            last_dir_change: 0,
+            previous_update: 0,
+        })
+    }
+
+    fn get_clear_shake(&self, lines: usize, t_spin: bool) -> Shake {
+        let shake = match lines {
+            1 => self.config.single,
+            2 => self.config.double,
+            3 => self.config.triple,
+            _ => self.config.tetris,
+        };
+
+        if !t_spin {
+            return shake;
+        }
+
+        Shake {
+            amplitude: shake.amplitude * self.config.t_spin_scale,
+            duration: (shake.duration as f32 * self.config.t_spin_scale) as u32,
+        }
+    }
+
+    fn get_hard_drop_shake(&self, distance: u8) -> Shake {
+        Shake {
+            amplitude: self.config.hard_drop.amplitude * distance as f32 / PLAYFIELD_HEIGHT as f32,
+            duration: self.config.hard_drop.duration,
+        }
+    }
+
+    // a weaker shake doesn't cut a stronger one short
+    fn trigger(&mut self, shake: Shake) {
+        if let (Some(current), Some(start)) = (self.shake, self.start) {
+            if self.get_amplitude(current, start, self.previous_update) >= shake.amplitude * self.intensity {
+                return;
+            }
+        }
+
+        self.shake = Some(shake);
+        self.start = None;
+    }
+
+    pub fn handle_event(&mut self, event: &BoardEvent) {
+        let shake = match event {
+            BoardEvent::LinesCleared { rows, t_spin } => self.get_clear_shake(rows.len(), *t_spin),
+            BoardEvent::HardDropped { distance, .. } if *distance != 0 => self.get_hard_drop_shake(*distance),
+            // an empty shake to put the view back in place on the next update
+            BoardEvent::GameReset => {
+                self.shake = Some(Shake { amplitude: 0.0, duration: 0 });
+                self.start = None;
+                return;
+            }
+            _ => return,
+        };
+
+        self.trigger(shake);
+    }
+
+    // eases out quadratically so the shake settles instead of stopping dead
+    fn get_amplitude(&self, shake: Shake, start: u64, time: u64) -> f32 {
+        let progress = (time.saturating_sub(start) as f32 / shake.duration.max(1) as f32).min(1.0);
+
+        shake.amplitude * self.intensity * (1.0 - progress).powi(2)
+    }
+
+    fn get_random_point(&mut self) -> (f32, f32) {
+        let angle = self.rng.random_range(0.0..std::f32::consts::TAU);
+
+        (angle.cos(), angle.sin())
+    }
+
+    // the offset to move the view by, None once the shake is over and the view is back in place
+    pub fn update(&mut self, time: u64) -> Option<(f32, f32)> {
+        self.previous_update = time;
+
+        let shake = self.shake?;
+        let start = *self.start.get_or_insert(time);
+
+        if time.saturating_sub(start) >= shake.duration as u64 {
+            self.shake = None;
+            self.start = None;
+
+            return Some((0.0, 0.0));
+        }
+
+        if time.saturating_sub(self.last_dir_change) >= self.config.interval as u64 {
+            self.last_dir_change = time;
+            self.previous_point = self.next_point;
+            self.next_point = self.get_random_point();
+        }
+
+        let a = (time.saturating_sub(self.last_dir_change) as f32 / self.config.interval.max(1) as f32).min(1.0);
+        let amplitude = self.get_amplitude(shake, start, time);
+
+        Some((
+            lerp(self.previous_point.0, self.next_point.0, a) * amplitude,
+            lerp(self.previous_point.1, self.next_point.1, a) * amplitude,
+        ))
+    }
+}
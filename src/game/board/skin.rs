@@ -0,0 +1,67 @@
+use serde::Deserialize;
+use std::{collections::HashMap, fs};
+
+use super::*;
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    pub path: String,
+}
+
+// One colour per shape, the guideline colours unless a skin says otherwise
+#[derive(Deserialize, Debug, Clone)]
+pub struct Palette {
+    colors: HashMap<TetrominoShape, [u8; 3]>,
+}
+
+impl Default for Palette {
+    fn default() -> Palette {
+        Palette {
+            colors: HashMap::from([
+                (TetrominoShape::I, [0, 255, 255]),
+                (TetrominoShape::J, [0, 0, 255]),
+                (TetrominoShape::L, [255, 127, 0]),
+                (TetrominoShape::O, [255, 255, 0]),
+                (TetrominoShape::S, [0, 255, 0]),
+                (TetrominoShape::T, [127, 0, 255]),
+                (TetrominoShape::Z, [255, 0, 0]),
+            ]),
+        }
+    }
+}
+
+impl Palette {
+    pub fn get_color(&self, shape: TetrominoShape) -> [u8; 3] {
+        self.colors[&shape]
+    }
+}
+
+// How the blocks look, picked by conf/skin.json from the files in conf/skins
+#[derive(Deserialize, Debug, Clone)]
+pub struct Skin {
+    pub name: String,
+    pub texture: String,
+    pub palette: Palette,
+}
+
+impl Skin {
+    pub fn load(path: &str) -> Skin {
+        let contents = fs::read_to_string(path).expect("Failed to read the skin file");
+
+        let skin: Skin = serde_json::from_str(&contents).expect("Could not parse skin JSON");
+
+        for shape in Palette::default().colors.keys() {
+            assert!(skin.palette.colors.contains_key(shape), "The {} skin has no colour for {shape:?}", skin.name);
+        }
+
+        skin
+    }
+
+    pub fn from_config(path: &str) -> Skin {
+        let contents = fs::read_to_string(path).expect("Failed to read the skin config file");
+
+        let conf: Config = serde_json::from_str(&contents).expect("Could not parse skin JSON config");
+
+        Skin::load(&conf.path)
+    }
+}
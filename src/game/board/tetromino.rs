@@ -1,8 +1,9 @@
 use rand::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::types::*;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum TetrominoShape {
     I,
     J,
@@ -14,7 +15,7 @@ pub enum TetrominoShape {
 }
 
 impl TetrominoShape {
-    pub fn rand(rng: &mut ThreadRng, prev_shape: TetrominoShape) -> TetrominoShape {
+    pub fn rand(rng: &mut impl Rng, prev_shape: TetrominoShape) -> TetrominoShape {
         let types = [
             TetrominoShape::I,
             TetrominoShape::J,
@@ -34,7 +35,7 @@ impl TetrominoShape {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Orientation{
     ZERO = 0,
     RIGHT = 1,
@@ -42,6 +43,7 @@ pub enum Orientation{
     LEFT = 3
 }
 
+#[derive(Clone, Copy, Debug)]
 pub struct Tetromino {
     shape: TetrominoShape,
 
@@ -61,17 +63,33 @@ impl Tetromino {
         }
     }
 
+    // Puts a new piece as high up as it fits, pushing it into bounds first
+    pub fn spawn(pos: (i8, i8), color: [u8; 3], shape: TetrominoShape, grid: &Grid) -> Tetromino {
+        let mut tetromino = Tetromino::new(pos, color, shape);
+
+        let mut scalar = 0;
+
+
+        while !tetromino.is_in_bounds() {
+            tetromino.translate((scalar, scalar), grid);
+            scalar += 1;
+        }
+
+        while tetromino.translate((0, -1), grid){}
+
+        tetromino
+    }
 
     fn get_raw_data(&self) -> [(i8, i8); 4] {
 
         let mut shape_data: [(i8, i8); 4] = match self.shape{
             TetrominoShape::I => [(1, 0), (0, 0), (-1, 0), (-2, 0)],
-            TetrominoShape::J => [(1, -1), (1, 0), (0, 0), (-1, 0)],
-            TetrominoShape::L => [(-1, -1), (1, 0), (0, 0), (-1, 0)],
+            TetrominoShape::J => [(-1, -1), (1, 0), (0, 0), (-1, 0)],
+            TetrominoShape::L => [(1, -1), (1, 0), (0, 0), (-1, 0)],
             TetrominoShape::O => [(0, 0), (0, -1), (-1, 0), (-1, -1)],
-            TetrominoShape::S => [(1, 0), (0 ,0), (0, -1), (-1, -1)],
+            TetrominoShape::S => [(1, -1), (0, -1), (0, 0), (-1, 0)],
             TetrominoShape::T => [(1, 0), (0, 0), (0, -1), (-1, 0)],
-            TetrominoShape::Z => [(1, -1), (0, -1), (0, 0), (-1, 0)]
+            TetrominoShape::Z => [(1, 0), (0 ,0), (0, -1), (-1, -1)]
         };
 
         for block in shape_data.iter_mut(){
@@ -316,6 +334,14 @@ impl Tetromino {
         true
     }
 
+    pub fn get_pos(&self) -> (i8, i8) {
+        self.pos
+    }
+
+    pub fn get_orientation(&self) -> Orientation {
+        self.orientation
+    }
+
     pub fn get_color(&self) -> [u8; 4] {
         self.color
     }
@@ -0,0 +1,20 @@
+use serde::Deserialize;
+use std::fs;
+
+// Delays in milliseconds around the moments a piece isn't falling
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct Timing {
+    pub ready_time: u32,
+    pub go_time: u32,
+
+    pub entry_delay: u32,
+    pub line_clear_delay: u32,
+}
+
+impl Timing {
+    pub fn load(path: &str) -> Timing {
+        let contents = fs::read_to_string(path).expect("Failed to read the timing config file");
+
+        serde_json::from_str(&contents).expect("Could not parse timing JSON config")
+    }
+}
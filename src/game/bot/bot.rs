@@ -0,0 +1,92 @@
+use serde::Deserialize;
+use std::fs;
+
+use super::heuristic::{apply_placement, evaluate};
+use super::placement::{find_placement, find_placements};
+use super::{Controller, Placement, Weights};
+use crate::game::board::{Tetromino, TetrominoShape};
+use crate::game::input::Action;
+use crate::types::*;
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    pub weights: Weights,
+    pub input_interval: u32,
+}
+
+pub struct Bot {
+    weights: Weights,
+
+    input_interval: u32,
+    previous_input: u128,
+
+    target: Option<(TetrominoShape, [(u8, u8); 4])>,
+}
+
+impl Bot {
+    fn load_config(path: &str) -> Config {
+        let contents = fs::read_to_string(path).expect("Failed to read the bot config file");
+
+        serde_json::from_str(&contents).expect("Could not parse bot JSON config")
+    }
+
+    pub fn new(path: &str) -> Bot {
+        let conf = Bot::load_config(path);
+
+        Bot {
+            weights: conf.weights,
+            input_interval: conf.input_interval,
+            previous_input: 0,
+            target: None,
+        }
+    }
+
+    fn score_placement(&self, placement: &Placement, grid: &Grid) -> f32 {
+        let (grid, lines) = apply_placement(grid, &placement.tetromino);
+
+        evaluate(&grid, lines, &self.weights)
+    }
+
+    fn pick_best(&self, placements: Vec<Placement>, grid: &Grid) -> Option<Placement> {
+        placements
+            .into_iter()
+            .map(|placement| (self.score_placement(&placement, grid), placement))
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, placement)| placement)
+    }
+
+    pub fn choose_placement(&self, tetromino: &Tetromino, grid: &Grid) -> Option<Placement> {
+        self.pick_best(find_placements(tetromino, grid), grid)
+    }
+}
+
+impl Controller for Bot {
+    // Gravity can move the piece between presses, so the path to the chosen cells is searched again every step
+    fn update(&mut self, tetromino: &Tetromino, _piece: u64, grid: &Grid, curr: u128) -> Option<Action> {
+        if self.previous_input + self.input_interval as u128 >= curr {
+            return None;
+        }
+
+        self.previous_input = curr;
+
+        let planned = match self.target {
+            Some((shape, cells)) if shape == tetromino.get_shape() => find_placement(tetromino, grid, &cells),
+            _ => None,
+        };
+
+        let placement = match planned {
+            Some(placement) => placement,
+            None => self.pick_best(find_placements(tetromino, grid), grid)?,
+        };
+
+        let action = placement.inputs[0];
+
+        self.target = if action == Action::HardDrop {
+            None
+        } else {
+            Some((tetromino.get_shape(), placement.get_cells()))
+        };
+
+        Some(action)
+    }
+}
@@ -0,0 +1,9 @@
+use crate::game::board::Tetromino;
+use crate::game::input::Action;
+use crate::types::*;
+
+// Anything that can play the board by taking the same actions as a player, polled once per update.
+// `piece` counts every piece dealt, across games too, so a new piece can be told apart even when it has the last one's shape.
+pub trait Controller {
+    fn update(&mut self, tetromino: &Tetromino, piece: u64, grid: &Grid, curr: u128) -> Option<Action>;
+}
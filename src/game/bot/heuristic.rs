@@ -0,0 +1,77 @@
+use serde::Deserialize;
+
+use crate::game::board::Tetromino;
+use crate::types::*;
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct Weights {
+    pub height: f32,
+    pub lines: f32,
+    pub holes: f32,
+    pub bumpiness: f32,
+}
+
+// Locks the piece into a copy of the grid and clears full rows the same way the board does
+pub fn apply_placement(grid: &Grid, tetromino: &Tetromino) -> (Grid, u32) {
+    let mut grid = *grid;
+
+    for pos in tetromino.get_data().chunks(2) {
+        grid[pos[1] as usize][pos[0] as usize] = tetromino.get_color();
+    }
+
+    let mut lines = 0u32;
+
+    for y in 0..PLAYFIELD_HEIGHT {
+        if grid[y].iter().any(|block| *block == [0; 4]) {
+            continue;
+        }
+
+        lines += 1;
+
+        for y_new in (0..y).rev() {
+            grid[y_new + 1] = grid[y_new];
+        }
+    }
+
+    (grid, lines)
+}
+
+fn get_column_heights(grid: &Grid) -> [u32; PLAYFIELD_WIDTH] {
+    let mut heights = [0u32; PLAYFIELD_WIDTH];
+
+    for (x, height) in heights.iter_mut().enumerate() {
+        if let Some(y) = (0..PLAYFIELD_HEIGHT).find(|y| grid[*y][x] != [0; 4]) {
+            *height = (PLAYFIELD_HEIGHT - y) as u32;
+        }
+    }
+
+    heights
+}
+
+// An empty cell counts as a hole when anything sits above it in the same column
+fn count_holes(grid: &Grid, heights: &[u32; PLAYFIELD_WIDTH]) -> u32 {
+    let mut holes = 0u32;
+
+    for (x, height) in heights.iter().enumerate() {
+        let top = PLAYFIELD_HEIGHT - *height as usize;
+
+        holes += (top..PLAYFIELD_HEIGHT)
+            .filter(|y| grid[*y][x] == [0; 4])
+            .count() as u32;
+    }
+
+    holes
+}
+
+pub fn evaluate(grid: &Grid, lines: u32, weights: &Weights) -> f32 {
+    let heights = get_column_heights(grid);
+
+    let aggregate_height: u32 = heights.iter().sum();
+    let bumpiness: u32 = heights.windows(2).map(|pair| pair[0].abs_diff(pair[1])).sum();
+    let holes = count_holes(grid, &heights);
+
+    weights.height * aggregate_height as f32
+        + weights.lines * lines as f32
+        + weights.holes * holes as f32
+        + weights.bumpiness * bumpiness as f32
+}
@@ -0,0 +1,16 @@
+mod bot;
+pub use bot::Bot;
+
+mod controller;
+pub use controller::Controller;
+
+mod heuristic;
+pub use heuristic::Weights;
+
+mod placement;
+pub use placement::Placement;
+
+mod tbp_messages;
+
+mod tbp_bot;
+pub use tbp_bot::TbpBot;
@@ -0,0 +1,103 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::game::board::{Orientation, Tetromino};
+use crate::game::input::Action;
+use crate::types::*;
+
+const MOVES: [Action; 4] = [Action::MoveLeft, Action::MoveRight, Action::RotateCW, Action::SoftDrop];
+
+pub struct Placement {
+    pub tetromino: Tetromino,
+    pub inputs: Vec<Action>,
+}
+
+impl Placement {
+    pub fn get_cells(&self) -> [(u8, u8); 4] {
+        get_cells(&self.tetromino)
+    }
+}
+
+// Orientations of symmetric pieces can cover the same cells, so placements are compared by their sorted blocks
+fn get_cells(tetromino: &Tetromino) -> [(u8, u8); 4] {
+    let data = tetromino.get_data();
+
+    let mut cells = [(0u8, 0u8); 4];
+    for (i, pos) in data.chunks(2).enumerate() {
+        cells[i] = (pos[0], pos[1]);
+    }
+
+    cells.sort_unstable();
+
+    cells
+}
+
+fn apply_move(tetromino: &mut Tetromino, action: Action, grid: &Grid) -> bool {
+    match action {
+        Action::MoveLeft => tetromino.translate((-1, 0), grid),
+        Action::MoveRight => tetromino.translate((1, 0), grid),
+
+        Action::RotateCW => {
+            let previous = (tetromino.get_pos(), tetromino.get_orientation());
+            tetromino.rotate(Orientation::RIGHT, grid);
+
+            previous != (tetromino.get_pos(), tetromino.get_orientation())
+        }
+
+        // pressing down on a grounded piece locks it, so a soft drop only counts while the piece can still fall
+        Action::SoftDrop => !tetromino.is_grounded(grid) && tetromino.translate((0, 1), grid),
+
+        _ => false,
+    }
+}
+
+// Walks every state the piece can be steered into with the board's own movement rules and hard drops from each one.
+// The search is breadth first, so every placement comes with the shortest action sequence that reaches it.
+pub fn find_placements(tetromino: &Tetromino, grid: &Grid) -> Vec<Placement> {
+    let mut visited = HashSet::<((i8, i8), Orientation)>::new();
+    let mut found = HashSet::<[(u8, u8); 4]>::new();
+
+    let mut placements = Vec::<Placement>::new();
+    let mut queue = VecDeque::<(Tetromino, Vec<Action>)>::new();
+
+    visited.insert((tetromino.get_pos(), tetromino.get_orientation()));
+    queue.push_back((*tetromino, Vec::new()));
+
+    while let Some((curr, inputs)) = queue.pop_front() {
+        let mut dropped = curr;
+        while dropped.translate((0, 1), grid) {}
+
+        if found.insert(get_cells(&dropped)) {
+            let mut drop_inputs = inputs.clone();
+            drop_inputs.push(Action::HardDrop);
+
+            placements.push(Placement {
+                tetromino: dropped,
+                inputs: drop_inputs,
+            });
+        }
+
+        for action in MOVES {
+            let mut next = curr;
+
+            if !apply_move(&mut next, action, grid) {
+                continue;
+            }
+
+            if visited.insert((next.get_pos(), next.get_orientation())) {
+                let mut next_inputs = inputs.clone();
+                next_inputs.push(action);
+
+                queue.push_back((next, next_inputs));
+            }
+        }
+    }
+
+    placements
+}
+
+// The shortest way of steering the piece onto exactly these cells, if there is one
+pub fn find_placement(tetromino: &Tetromino, grid: &Grid, cells: &[(u8, u8); 4]) -> Option<Placement> {
+    find_placements(tetromino, grid)
+        .into_iter()
+        .find(|placement| placement.get_cells() == *cells)
+}
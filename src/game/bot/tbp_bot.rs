@@ -0,0 +1,329 @@
+use serde::Deserialize;
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::heuristic::apply_placement;
+use super::placement::find_placement;
+use super::tbp_messages::*;
+use super::Controller;
+use crate::game::board::Tetromino;
+use crate::game::input::Action;
+use crate::types::*;
+
+// how long a bot gets to exit after being told to quit before it's killed
+const QUIT_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    pub enabled: bool,
+    pub command: String,
+    pub args: Vec<String>,
+    pub input_interval: u32,
+}
+
+enum Phase {
+    WaitingForInfo,
+    WaitingForReady,
+    Idle,
+    WaitingForSuggestion,
+    Playing([(u8, u8); 4]),
+    Disconnected,
+}
+
+// Drives the board with an external bot speaking the Tetris Bot Protocol over its stdin/stdout.
+// The board has no preview or hold, so the queue a bot sees is always just the current piece.
+pub struct TbpBot {
+    child: Child,
+    stdin: ChildStdin,
+    messages: Receiver<BotMessage>,
+
+    phase: Phase,
+    blocking: bool,
+
+    input_interval: u32,
+    previous_input: u128,
+
+    // the playfield's count of the piece the bot was last told about
+    current_piece: Option<u64>,
+    // what the grid should look like once the last played move locks, a mismatch means the bot is out of sync
+    expected_grid: Option<Grid>,
+}
+
+impl TbpBot {
+    fn load_config(path: &str) -> Config {
+        let contents = fs::read_to_string(path).expect("Failed to read the TBP config file");
+
+        serde_json::from_str(&contents).expect("Could not parse TBP JSON config")
+    }
+
+    pub fn from_config(path: &str) -> Option<TbpBot> {
+        let conf = TbpBot::load_config(path);
+
+        if !conf.enabled {
+            return None;
+        }
+
+        // the heuristic bot takes over if the configured one can't be started
+        match TbpBot::new(&conf.command, &conf.args, conf.input_interval) {
+            Ok(tbp_bot) => Some(tbp_bot),
+            Err(err) => {
+                eprintln!("Failed to start the TBP bot {}: {err}", conf.command);
+                None
+            }
+        }
+    }
+
+    pub fn new(command: &str, args: &[String], input_interval: u32) -> io::Result<TbpBot> {
+        let mut child = Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let stdin = child.stdin.take().expect("Failed to open the TBP bot's stdin");
+        let stdout = child.stdout.take().expect("Failed to open the TBP bot's stdout");
+
+        let (sender, messages) = mpsc::channel();
+
+        // the bot answers whenever it likes, so its output is read on a separate thread
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    return;
+                };
+
+                match serde_json::from_str::<BotMessage>(&line) {
+                    Ok(message) => {
+                        if sender.send(message).is_err() {
+                            return;
+                        }
+                    }
+                    Err(err) => eprintln!("Ignoring a TBP message the bot sent: {err}"),
+                }
+            }
+        });
+
+        Ok(TbpBot {
+            child,
+            stdin,
+            messages,
+            phase: Phase::WaitingForInfo,
+            blocking: false,
+            input_interval,
+            previous_input: 0,
+            current_piece: None,
+            expected_grid: None,
+        })
+    }
+
+    // Makes every update wait for the bot's answer, for simulations that run faster than real time
+    pub fn set_blocking(&mut self, blocking: bool) {
+        self.blocking = blocking;
+    }
+
+    fn send(&mut self, message: &FrontendMessage) {
+        let mut line = serde_json::to_string(message).expect("Failed to serialize a TBP message");
+        line.push('\n');
+
+        if self.stdin.write_all(line.as_bytes()).and_then(|()| self.stdin.flush()).is_err() {
+            eprintln!("Lost the connection to the TBP bot");
+            self.phase = Phase::Disconnected;
+        }
+    }
+
+    fn is_waiting(&self) -> bool {
+        matches!(
+            self.phase,
+            Phase::WaitingForInfo | Phase::WaitingForReady | Phase::WaitingForSuggestion
+        )
+    }
+
+    fn receive(&mut self) -> Option<BotMessage> {
+        if self.blocking && self.is_waiting() {
+            return match self.messages.recv() {
+                Ok(message) => Some(message),
+                Err(_) => {
+                    self.phase = Phase::Disconnected;
+                    None
+                }
+            };
+        }
+
+        match self.messages.try_recv() {
+            Ok(message) => Some(message),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                self.phase = Phase::Disconnected;
+                None
+            }
+        }
+    }
+
+    fn handle_suggestion(&mut self, moves: &[Move], tetromino: &Tetromino, grid: &Grid) {
+        // the first suggested move the piece can actually reach from where it is now wins
+        for piece_move in moves {
+            let Some(cells) = get_location_cells(&piece_move.location) else {
+                continue;
+            };
+
+            let Some(placement) = find_placement(tetromino, grid, &cells) else {
+                continue;
+            };
+
+            self.expected_grid = Some(apply_placement(grid, &placement.tetromino).0);
+            self.phase = Phase::Playing(cells);
+
+            self.send(&FrontendMessage::Play {
+                piece_move: *piece_move,
+            });
+
+            return;
+        }
+
+        eprintln!("The TBP bot suggested no reachable placement, restarting it on the next piece");
+
+        self.expected_grid = None;
+        self.phase = Phase::Idle;
+    }
+
+    fn handle_messages(&mut self, tetromino: &Tetromino, piece: u64, grid: &Grid) {
+        while let Some(message) = self.receive() {
+            match message {
+                BotMessage::Info { name, version, author, .. } => {
+                    println!("Connected to the TBP bot {name} {version} by {author}");
+
+                    self.phase = Phase::WaitingForReady;
+                    self.send(&FrontendMessage::Rules {});
+                }
+
+                BotMessage::Ready => {
+                    self.phase = Phase::Idle;
+                }
+
+                BotMessage::Error { reason } => {
+                    eprintln!("The TBP bot reported an error: {reason}");
+
+                    self.phase = Phase::Disconnected;
+                }
+
+                BotMessage::Suggestion { moves } => {
+                    if !matches!(self.phase, Phase::WaitingForSuggestion) {
+                        continue;
+                    }
+
+                    // an answer for a piece that has since locked or been reset away is dropped, the new piece is sent next
+                    if self.current_piece == Some(piece) {
+                        self.handle_suggestion(&moves, tetromino, grid);
+                    } else {
+                        self.phase = Phase::Idle;
+                    }
+                }
+            }
+
+            if matches!(self.phase, Phase::Disconnected) {
+                return;
+            }
+        }
+    }
+
+    // Tells the bot about a freshly spawned piece, restarting it from the current grid if the last move didn't land as planned
+    fn handle_new_piece(&mut self, tetromino: &Tetromino, piece: u64, grid: &Grid) {
+        let is_synced = self.expected_grid.is_some_and(|expected| expected == *grid);
+
+        if is_synced {
+            self.send(&FrontendMessage::NewPiece {
+                piece: tetromino.get_shape(),
+            });
+        } else {
+            if self.current_piece.is_some() {
+                self.send(&FrontendMessage::Stop);
+            }
+
+            self.send(&FrontendMessage::Start {
+                hold: None,
+                queue: vec![tetromino.get_shape()],
+                combo: 0,
+                back_to_back: false,
+                board: get_tbp_board(grid),
+            });
+        }
+
+        self.current_piece = Some(piece);
+        self.expected_grid = None;
+
+        self.send(&FrontendMessage::Suggest);
+
+        if !matches!(self.phase, Phase::Disconnected) {
+            self.phase = Phase::WaitingForSuggestion;
+        }
+    }
+}
+
+impl Controller for TbpBot {
+    fn update(&mut self, tetromino: &Tetromino, piece: u64, grid: &Grid, curr: u128) -> Option<Action> {
+        if matches!(self.phase, Phase::Disconnected) {
+            return None;
+        }
+
+        self.handle_messages(tetromino, piece, grid);
+
+        // a new piece waits for the bot to answer for the last one, so an answer is never taken for the wrong piece.
+        // After a reset the grid won't be the expected one, which restarts the bot on the new game.
+        if matches!(self.phase, Phase::Idle | Phase::Playing(_)) && self.current_piece != Some(piece) {
+            self.handle_new_piece(tetromino, piece, grid);
+            self.handle_messages(tetromino, piece, grid);
+        }
+
+        if self.previous_input + self.input_interval as u128 >= curr {
+            return None;
+        }
+
+        let Phase::Playing(cells) = self.phase else {
+            return None;
+        };
+
+        self.previous_input = curr;
+
+        let Some(placement) = find_placement(tetromino, grid, &cells) else {
+            // the piece drifted somewhere the move can't be reached from anymore
+            self.phase = Phase::Idle;
+            return Some(Action::HardDrop);
+        };
+
+        let action = placement.inputs[0];
+
+        if action == Action::HardDrop {
+            self.phase = Phase::Idle;
+        }
+
+        Some(action)
+    }
+}
+
+impl Drop for TbpBot {
+    fn drop(&mut self) {
+        if !matches!(self.phase, Phase::Disconnected) {
+            self.send(&FrontendMessage::Quit);
+
+            let deadline = Instant::now() + QUIT_TIMEOUT;
+
+            while Instant::now() < deadline {
+                if !matches!(self.child.try_wait(), Ok(None)) {
+                    return;
+                }
+
+                thread::sleep(Duration::from_millis(10));
+            }
+        }
+
+        // a bot that ignores quit would otherwise keep the game from closing
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::board::TetrominoShape;
+use crate::types::*;
+
+// Message types of the Tetris Bot Protocol, one JSON object per line on the bot's stdin/stdout
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TbpOrientation {
+    North,
+    East,
+    South,
+    West,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Spin {
+    None,
+    Mini,
+    Full,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct PieceLocation {
+    #[serde(rename = "type")]
+    pub shape: TetrominoShape,
+    pub orientation: TbpOrientation,
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct Move {
+    pub location: PieceLocation,
+    pub spin: Spin,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FrontendMessage {
+    Rules {},
+    Start {
+        hold: Option<TetrominoShape>,
+        queue: Vec<TetrominoShape>,
+        combo: u32,
+        back_to_back: bool,
+        board: Vec<Vec<Option<&'static str>>>,
+    },
+    Suggest,
+    Play {
+        #[serde(rename = "move")]
+        piece_move: Move,
+    },
+    NewPiece {
+        piece: TetrominoShape,
+    },
+    Stop,
+    Quit,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BotMessage {
+    Info {
+        name: String,
+        version: String,
+        author: String,
+        #[serde(default)]
+        features: Vec<String>,
+    },
+    Ready,
+    Error {
+        reason: String,
+    },
+    Suggestion {
+        moves: Vec<Move>,
+    },
+}
+
+// TBP boards are always this tall, the rows above the playfield are sent empty
+const TBP_BOARD_HEIGHT: usize = 40;
+
+// TBP rows count up from the floor and only care whether a cell is filled, so every block is sent as garbage
+pub fn get_tbp_board(grid: &Grid) -> Vec<Vec<Option<&'static str>>> {
+    let mut board: Vec<Vec<Option<&'static str>>> = grid
+        .iter()
+        .rev()
+        .map(|row| {
+            row.iter()
+                .map(|block| if *block == [0; 4] { None } else { Some("G") })
+                .collect()
+        })
+        .collect();
+
+    board.resize(TBP_BOARD_HEIGHT.max(board.len()), vec![None; PLAYFIELD_WIDTH]);
+
+    board
+}
+
+// The cells a TBP location covers, in grid coordinates and sorted like Placement::get_cells.
+// TBP describes every piece by fixed offsets around its centre, so this doesn't depend on how Tetromino stores rotations.
+pub fn get_location_cells(location: &PieceLocation) -> Option<[(u8, u8); 4]> {
+    let offsets: [(i32, i32); 4] = match location.shape {
+        TetrominoShape::I => [(-1, 0), (0, 0), (1, 0), (2, 0)],
+        TetrominoShape::O => [(0, 0), (1, 0), (0, 1), (1, 1)],
+        TetrominoShape::T => [(-1, 0), (0, 0), (1, 0), (0, 1)],
+        TetrominoShape::L => [(-1, 0), (0, 0), (1, 0), (1, 1)],
+        TetrominoShape::J => [(-1, 0), (0, 0), (1, 0), (-1, 1)],
+        TetrominoShape::S => [(-1, 0), (0, 0), (0, 1), (1, 1)],
+        TetrominoShape::Z => [(-1, 1), (0, 1), (0, 0), (1, 0)],
+    };
+
+    let mut cells = [(0u8, 0u8); 4];
+
+    for (i, offset) in offsets.iter().enumerate() {
+        let (x, y) = match location.orientation {
+            TbpOrientation::North => (offset.0, offset.1),
+            TbpOrientation::East => (offset.1, -offset.0),
+            TbpOrientation::South => (-offset.0, -offset.1),
+            TbpOrientation::West => (-offset.1, offset.0),
+        };
+
+        let x = location.x + x;
+        let y = PLAYFIELD_HEIGHT as i32 - 1 - (location.y + y);
+
+        if x < 0 || x >= PLAYFIELD_WIDTH as i32 || y < 0 || y >= PLAYFIELD_HEIGHT as i32 {
+            return None;
+        }
+
+        cells[i] = (x as u8, y as u8);
+    }
+
+    cells.sort_unstable();
+
+    Some(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_location_cells, get_tbp_board, PieceLocation, TbpOrientation};
+    use crate::game::board::TetrominoShape;
+    use crate::types::{Grid, PLAYFIELD_HEIGHT, PLAYFIELD_WIDTH};
+
+    fn get_location(shape: TetrominoShape, orientation: TbpOrientation, x: i32, y: i32) -> PieceLocation {
+        PieceLocation { shape, orientation, x, y }
+    }
+
+    #[test]
+    fn tbp_board_counts_up_from_the_floor() {
+        let mut grid: Grid = [[[0; 4]; PLAYFIELD_WIDTH]; PLAYFIELD_HEIGHT];
+        grid[PLAYFIELD_HEIGHT - 1][0] = [255, 0, 0, 255];
+        grid[0][PLAYFIELD_WIDTH - 1] = [0, 255, 0, 255];
+
+        let board = get_tbp_board(&grid);
+
+        assert_eq!(board[0][0], Some("G"));
+        assert_eq!(board[0][1], None);
+        assert_eq!(board[PLAYFIELD_HEIGHT - 1][PLAYFIELD_WIDTH - 1], Some("G"));
+
+        // the rows above the playfield are padded out to the full 40
+        assert_eq!(board.len(), 40);
+        assert!(board[PLAYFIELD_HEIGHT..].iter().all(|row| row.len() == PLAYFIELD_WIDTH && row.iter().all(Option::is_none)));
+    }
+
+    #[test]
+    fn location_cells_flip_to_grid_rows() {
+        let cells = get_location_cells(&get_location(TetrominoShape::T, TbpOrientation::North, 4, 0));
+
+        let bottom = PLAYFIELD_HEIGHT as u8 - 1;
+        assert_eq!(cells, Some([(3, bottom), (4, bottom - 1), (4, bottom), (5, bottom)]));
+    }
+
+    #[test]
+    fn location_cells_rotate_around_the_centre() {
+        let cells = get_location_cells(&get_location(TetrominoShape::I, TbpOrientation::East, 0, 5));
+
+        let centre = PLAYFIELD_HEIGHT as u8 - 1 - 5;
+        assert_eq!(cells, Some([(0, centre - 1), (0, centre), (0, centre + 1), (0, centre + 2)]));
+
+        let cells = get_location_cells(&get_location(TetrominoShape::S, TbpOrientation::South, 5, 1));
+
+        let row = PLAYFIELD_HEIGHT as u8 - 1 - 1;
+        assert_eq!(cells, Some([(4, row + 1), (5, row), (5, row + 1), (6, row)]));
+    }
+
+    #[test]
+    fn location_cells_outside_the_playfield_are_rejected() {
+        assert_eq!(get_location_cells(&get_location(TetrominoShape::I, TbpOrientation::North, 0, 0)), None);
+        assert_eq!(get_location_cells(&get_location(TetrominoShape::O, TbpOrientation::North, 4, -1)), None);
+    }
+}
@@ -82,9 +82,21 @@ impl<'a> ButtonManager {
     pub fn update(&mut self, device: &Device) {
         self.update_command_buffer.begin(device, &vk::CommandBufferInheritanceInfo::default(), vk::CommandBufferUsageFlags::empty());
 
+        // a different set of buttons needs differently sized buffers, the press states get rebuilt on the next update
         match &mut self.instance_buffer {
-            Some(buff) => buff.update(device, &mut self.update_command_buffer, &self.instance_data),
-            None => {
+            Some(buff) if buff.get_size() == self.instance_data.len() as u64 => buff.update(device, &mut self.update_command_buffer, &self.instance_data),
+            _ => {
+                if let Some(buff) = &mut self.instance_buffer {
+                    buff.destroy(device);
+                }
+
+                if let Some(buff) = &mut self.pressed_buffer {
+                    buff.destroy(device);
+                }
+
+                self.pressed_buffer = None;
+                self.last_press_update_frame = std::u32::MAX;
+
                 self.instance_buffer = Some(Buffer::new(device, &mut self.update_command_buffer, &self.instance_data, BufferType::Vertex, false));
             }
         };
@@ -2,12 +2,19 @@ use ::core::panic;
 
 use ash::vk;
 use button::ButtonManager;
+use particles::ParticleSystem;
 use descriptor::DescriptorSet;
 
 use super::*;
 use crate::*;
 
 use audio::*;
+use input::{Bindings, Gamepad};
+use scene::*;
+use settings::{AudioSettings, WindowSettings};
+use stats::Records;
+
+use std::time::{SystemTime, UNIX_EPOCH};
 
 macro_rules! device {
     ($x:ident) => {
@@ -15,6 +22,27 @@ macro_rules! device {
     };
 }
 
+// borrows the game field by field so the current scene can be called alongside it
+macro_rules! scene_context {
+    ($x:ident, $events:expr, $actions:expr, $curr:expr) => {
+        SceneContext {
+            device: $x.core.get_device(),
+            window: &mut $x.window,
+            board: &mut $x.board,
+            user_interface: &mut $x.user_interface,
+            audio_manager: &mut $x.audio_manager,
+            records: &mut $x.records,
+            bindings: &mut $x.bindings,
+            window_settings: &mut $x.window_settings,
+            audio_settings: &mut $x.audio_settings,
+            events: $events,
+            actions: $actions,
+            curr: $curr,
+            frame_count: $x.frame_count,
+        }
+    };
+}
+
 pub struct Game {
     window: Window,
 
@@ -36,16 +64,31 @@ pub struct Game {
 
     audio_manager: AudioManager,
 
-    soundtrack: Sound,
+    spectator: Option<SpectatorServer>,
+
+    scene: Box<dyn Scene>,
+    records: Option<Records>,
+    bindings: Bindings,
+    gamepad: Option<Gamepad>,
+
+    window_settings: WindowSettings,
+    // what the window and swapchain currently look like, scenes only change the settings above
+    applied_window_settings: WindowSettings,
+
+    audio_settings: AudioSettings,
+    applied_audio_settings: AudioSettings,
 
     frame_count: u32,
 }
 
 impl Game {
     pub fn new() -> Game {
-        let window = Window::new(720, 1280, "TETRIS");
+        let window_settings = WindowSettings::load("conf/window.json");
+
+        let mut window = Window::new(window_settings.width, window_settings.height, "TETRIS");
+        window.set_mode(window_settings.mode, window_settings.monitor, (window_settings.width, window_settings.height));
 
-        let core = Core::new(&window);
+        let core = Core::new(&window, window_settings.vsync);
 
         let set = Game::create_descriptor_set(&core);
         let render_pass = Game::create_render_pass(&core, &set);
@@ -68,15 +111,16 @@ impl Game {
         let image_acquisition_fence = Fence::new(core.get_device(), false);
         let render_finish_semaphore = Semaphore::new(core.get_device());
 
-        let mut audio_manager = AudioManager::new();
-
-        let mut soundtrack = Sound::new("music.wav", -20.0, true);
+        let audio_settings = AudioSettings::load("conf/audio.json");
+        let mut audio_manager = AudioManager::new(&audio_settings);
 
         let fence = Fence::new(core.get_device(), false);
 
-        // audio_manager.play(&mut soundtrack);
+        let spectator = SpectatorServer::from_config("conf/spectator.json");
+
+        audio_manager.start_music();
 
-        Game {
+        let mut game = Game {
             window,
             core,
             render_pass,
@@ -88,10 +132,30 @@ impl Game {
             image_acquisition_fence,
             render_finish_semaphore,
             audio_manager,
-            soundtrack,
+            spectator,
+            scene: Box::new(TitleScene::new()),
+            records: Records::from_config("conf/records.json"),
+            bindings: Bindings::load("conf/controls.json"),
+            gamepad: Gamepad::from_config("conf/gamepad.json"),
+            applied_window_settings: window_settings.clone(),
+            applied_audio_settings: audio_settings.clone(),
+            audio_settings,
+            window_settings,
             frame_count: 0,
             fence,
-        }
+        };
+
+        let curr = Game::get_curr();
+        game.scene.enter(&mut scene_context!(game, &[], &[], curr));
+
+        game
+    }
+
+    fn get_curr() -> u128 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis()
     }
 
     fn load_shaders(core: &Core) -> Vec<Shader> {
@@ -119,11 +183,18 @@ impl Game {
             String::from("shaders/bin/button_frag.spv"),
         );
 
+        let particle_shader = vulkan::Shader::new(
+            core.get_device(),
+            String::from("shaders/bin/particle_vert.spv"),
+            String::from("shaders/bin/particle_frag.spv"),
+        );
+
         vec![
             backdrop_shader,
             text_shader,
             button_shader,
             tetromino_shader,
+            particle_shader,
         ]
     }
 
@@ -163,13 +234,16 @@ impl Game {
             UserInterface::get_required_vertex_input_states();
         let (button_vertex_inputs, _button_vertex_input_data) =
             ButtonManager::get_required_vertex_input_states();
+        let (particle_vertex_inputs, _particle_vertex_input_data) =
+            ParticleSystem::get_required_vertex_input_states();
 
         let mut vertex_inputs = Vec::<vk::PipelineVertexInputStateCreateInfo>::with_capacity(
-            board_vertex_inputs.len() + ui_vertex_inputs.len(),
+            board_vertex_inputs.len() + ui_vertex_inputs.len() + button_vertex_inputs.len() + particle_vertex_inputs.len(),
         );
         vertex_inputs.extend_from_slice(&ui_vertex_inputs);
         vertex_inputs.extend_from_slice(&button_vertex_inputs);
         vertex_inputs.extend_from_slice(&board_vertex_inputs);
+        vertex_inputs.extend_from_slice(&particle_vertex_inputs);
 
         RenderPass::new(
             core.get_device(),
@@ -217,20 +291,76 @@ impl Game {
 
         self.update_descriptor_set();
 
-        self.board
-            .update(self.window.get_events(), &mut self.audio_manager);
-        self.user_interface.update(
-            self.board.get_game_state(),
-            &self.window,
-            self.core.get_device(),
-            &mut self.board,
-            self.frame_count,
-        );
+        let events: Vec<glfw::WindowEvent> = glfw::flush_messages(self.window.get_events())
+            .map(|event| event.1)
+            .collect();
+
+        self.window.handle_events(&events);
+
+        // alt+enter is taken out here so it doesn't also reach the bindings as enter
+        let (fullscreen_toggles, events): (Vec<glfw::WindowEvent>, Vec<glfw::WindowEvent>) = events.into_iter().partition(|event| {
+            matches!(event, glfw::WindowEvent::Key(glfw::Key::Enter, _, glfw::Action::Press, mods) if mods.contains(glfw::Modifiers::Alt))
+        });
+
+        if !fullscreen_toggles.is_empty() {
+            self.window_settings.toggle_fullscreen();
+        }
+
+        let mut actions = self.bindings.get_actions(&events);
+
+        if let Some(gamepad) = &mut self.gamepad {
+            actions.extend(gamepad.poll(self.window.get_glfw_context()));
+        }
+
+        let curr = Game::get_curr();
+
+        let mut context = scene_context!(self, &events, &actions, curr);
+
+        match self.scene.update(&mut context) {
+            Transition::Stay => (),
+            Transition::Switch(mut next) => {
+                self.scene.exit(&mut context);
+                next.enter(&mut context);
+                self.scene = next;
+            }
+            Transition::Quit => context.window.get_window_handle_mut().set_should_close(true),
+        }
+
+        if self.window_settings != self.applied_window_settings {
+            self.apply_window_settings();
+        }
+
+        if self.audio_settings != self.applied_audio_settings {
+            self.audio_manager.apply_settings(&self.audio_settings);
+            self.audio_settings.save();
+            self.applied_audio_settings = self.audio_settings.clone();
+        }
+
+        if let Some(spectator) = &mut self.spectator {
+            spectator.accept_clients();
+
+            if self.board.take_changed() {
+                spectator.publish(&BoardSnapshot::new(&self.board));
+            }
+
+            spectator.flush_clients();
+        }
+    }
+
+    // the swapchain is recreated before the next frame, vsync changes it even if the size stays the same
+    fn apply_window_settings(&mut self) {
+        let settings = self.window_settings.clone();
+
+        self.window.set_mode(settings.mode, settings.monitor, (settings.width, settings.height));
+        self.core.set_vsync(settings.vsync);
+        self.window.request_resize();
+
+        settings.save();
+        self.applied_window_settings = settings;
     }
 
-    fn get_image_index(&mut self) -> u32 {
-        let mut image_index = 0;
-        let mut is_swapchain_suboptimal = false;
+    // None when the swapchain is out of date, the frame is skipped and the swapchain recreated before the next one
+    fn get_image_index(&mut self) -> Option<u32> {
         unsafe {
             let result = self
                 .core
@@ -244,19 +374,14 @@ impl Game {
                     self.image_acquisition_fence.get_fence(),
                 );
 
-            match result {
-                Ok(res) => {
-                    image_index = res.0;
-                    is_swapchain_suboptimal = res.1
+            let (image_index, is_swapchain_suboptimal) = match result {
+                Ok(res) => res,
+                Err(vk::Result::ERROR_OUT_OF_DATE_KHR) => {
+                    self.window.request_resize();
+                    return None;
                 }
-                Err(err) => match err {
-                    vk::Result::ERROR_OUT_OF_DATE_KHR => {
-                        self.handle_resize();
-                    }
-
-                    _ => panic!("Failed to acquire the next swapchain image"),
-                },
-            }
+                Err(_) => panic!("Failed to acquire the next swapchain image"),
+            };
 
             device!(self)
                 .wait_for_fences(&[self.image_acquisition_fence.get_fence()], true, u64::MAX)
@@ -264,15 +389,14 @@ impl Game {
             device!(self)
                 .reset_fences(&[self.image_acquisition_fence.get_fence()])
                 .expect("Failed to reset the image acquisition fence");
-        }
-
-        if !is_swapchain_suboptimal {
-            return image_index;
-        }
 
-        self.handle_resize();
+            // a suboptimal image can still be presented, the swapchain is replaced after this frame
+            if is_swapchain_suboptimal {
+                self.window.request_resize();
+            }
 
-        image_index
+            Some(image_index)
+        }
     }
 
     fn reset_command_pool(&self) {
@@ -336,11 +460,43 @@ impl Game {
                 &begin_info,
                 vk::SubpassContents::INLINE,
             );
+
+            // the whole framebuffer is cleared but only the canvas is drawn on, the rest are the black bars
+            let extent = begin_info.render_area.extent;
+            let letterbox = Letterbox::new((extent.width, extent.height));
+            let (width, height) = letterbox.get_size();
+
+            device!(self).cmd_set_viewport(
+                self.command_buffer.get_command_buffer(),
+                0,
+                &[vk::Viewport {
+                    x: letterbox.x,
+                    y: letterbox.y,
+                    width,
+                    height,
+                    min_depth: 0.0,
+                    max_depth: 1.0,
+                }],
+            );
+
+            device!(self).cmd_set_scissor(
+                self.command_buffer.get_command_buffer(),
+                0,
+                &[vk::Rect2D {
+                    offset: vk::Offset2D { x: letterbox.x as i32, y: letterbox.y as i32 },
+                    extent: vk::Extent2D { width: width as u32, height: height as u32 },
+                }],
+            );
         }
     }
 
+    // nothing can still be using the old swapchain when it's replaced
     fn handle_resize(&mut self) {
-        self.window.update_size();
+        unsafe {
+            device!(self)
+                .device_wait_idle()
+                .expect("Failed to wait idle");
+        }
 
         self.core.recreate_swapchain(&self.window);
         self.render_pass
@@ -397,45 +553,40 @@ impl Game {
                 .queue_present(queue, &present_info)
         };
 
-        if matches!(result, Err(vk::Result::ERROR_OUT_OF_DATE_KHR)) {
+        if matches!(result, Err(vk::Result::ERROR_OUT_OF_DATE_KHR) | Ok(true)) {
             unsafe {
                 device!(self)
                     .queue_wait_idle(queue)
                     .expect("Failed to wait for the presentation queue"); // IT HAS TO FUCKING WAIT NOBODY TOLD ME THAT NOT EVEN THE SPEC
                                                                           //FUCK THIS AND THE 2 WEEKS I SPENT ON IT I CAnNOT
             }
-            self.handle_resize();
+            self.window.request_resize();
         }
     }
 
     fn render(&mut self) {
-        let image_index = self.get_image_index();
-
-        self.begin_command_buffer(image_index);
+        if self.window.take_resize() {
+            self.handle_resize();
+        }
 
-        self.user_interface.draw(
-            self.core.get_device(),
-            &self.render_pass,
-            &self.command_buffer,
-            0,
-            self.board.get_tetromino_instance_count(),
-        );
+        let Some(image_index) = self.get_image_index() else {
+            return;
+        };
 
-        unsafe {
-            device!(self).cmd_next_subpass(
-                self.command_buffer.get_command_buffer(),
-                vk::SubpassContents::INLINE,
-            );
-        }
+        self.begin_command_buffer(image_index);
 
-        self.board.draw(
+        let mut context = DrawContext::new(
             self.core.get_device(),
             &self.render_pass,
             &self.command_buffer,
-            3,
+            &mut self.user_interface,
+            &mut self.board,
             (self.window.get_glfw_context().get_time() * 1000.0) as u64,
         );
 
+        self.scene.draw(&mut context);
+        context.finish();
+
         self.end_command_buffer_and_present(image_index);
     }
 
@@ -443,6 +594,13 @@ impl Game {
         while !self.window.get_window_handle().should_close() {
             self.reset_command_pool();
             self.update();
+
+            // the game keeps updating while minimised but there's nothing to draw to
+            if self.window.is_minimized() {
+                self.window.get_glfw_context_mut().wait_events_timeout(0.1);
+                continue;
+            }
+
             self.render();
 
             self.frame_count += 1;
@@ -458,6 +616,9 @@ impl Drop for Game {
                 .expect("Failed to wait idle");
         }
 
+        let curr = Game::get_curr();
+        self.scene.exit(&mut scene_context!(self, &[], &[], curr));
+
         self.board.destruct(self.core.get_device());
         self.user_interface.destroy(self.core.get_device());
         self.render_pass.destroy(self.core.get_device());
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+// What a key press means to the game, so the board, the bots and the menus never look at keys directly
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    SoftDrop,
+    HardDrop,
+    RotateCW,
+    Pause,
+    Restart,
+    ToggleBot,
+    ToggleStats,
+    Confirm,
+    Back,
+}
+
+impl Action {
+    pub const ALL: [Action; 11] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::SoftDrop,
+        Action::HardDrop,
+        Action::RotateCW,
+        Action::Pause,
+        Action::Restart,
+        Action::ToggleBot,
+        Action::ToggleStats,
+        Action::Confirm,
+        Action::Back,
+    ];
+
+    // the font only has upper case letters and no spaces
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Action::MoveLeft => "LEFT",
+            Action::MoveRight => "RIGHT",
+            Action::SoftDrop => "SOFT-DROP",
+            Action::HardDrop => "HARD-DROP",
+            Action::RotateCW => "ROTATE",
+            Action::Pause => "PAUSE",
+            Action::Restart => "RESTART",
+            Action::ToggleBot => "BOT",
+            Action::ToggleStats => "STATS",
+            Action::Confirm => "CONFIRM",
+            Action::Back => "BACK",
+        }
+    }
+
+    // the ones that steer the falling piece, as opposed to the ones that drive the game around it
+    pub fn is_piece_move(&self) -> bool {
+        matches!(self, Action::MoveLeft | Action::MoveRight | Action::SoftDrop | Action::HardDrop | Action::RotateCW)
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::Action;
+use crate::game::settings::{save_settings, PersistedConfig};
+
+// Every key that can be bound, with the name it has in the config and on screen (so only characters the font has)
+const KEY_NAMES: [(glfw::Key, &str); 69] = [
+    (glfw::Key::A, "A"), (glfw::Key::B, "B"), (glfw::Key::C, "C"), (glfw::Key::D, "D"), (glfw::Key::E, "E"),
+    (glfw::Key::F, "F"), (glfw::Key::G, "G"), (glfw::Key::H, "H"), (glfw::Key::I, "I"), (glfw::Key::J, "J"),
+    (glfw::Key::K, "K"), (glfw::Key::L, "L"), (glfw::Key::M, "M"), (glfw::Key::N, "N"), (glfw::Key::O, "O"),
+    (glfw::Key::P, "P"), (glfw::Key::Q, "Q"), (glfw::Key::R, "R"), (glfw::Key::S, "S"), (glfw::Key::T, "T"),
+    (glfw::Key::U, "U"), (glfw::Key::V, "V"), (glfw::Key::W, "W"), (glfw::Key::X, "X"), (glfw::Key::Y, "Y"),
+    (glfw::Key::Z, "Z"),
+    (glfw::Key::Num0, "0"), (glfw::Key::Num1, "1"), (glfw::Key::Num2, "2"), (glfw::Key::Num3, "3"), (glfw::Key::Num4, "4"),
+    (glfw::Key::Num5, "5"), (glfw::Key::Num6, "6"), (glfw::Key::Num7, "7"), (glfw::Key::Num8, "8"), (glfw::Key::Num9, "9"),
+    (glfw::Key::F1, "F1"), (glfw::Key::F2, "F2"), (glfw::Key::F3, "F3"), (glfw::Key::F4, "F4"), (glfw::Key::F5, "F5"),
+    (glfw::Key::F6, "F6"), (glfw::Key::F7, "F7"), (glfw::Key::F8, "F8"), (glfw::Key::F9, "F9"), (glfw::Key::F10, "F10"),
+    (glfw::Key::F11, "F11"), (glfw::Key::F12, "F12"),
+    (glfw::Key::Left, "LEFT"), (glfw::Key::Right, "RIGHT"), (glfw::Key::Up, "UP"), (glfw::Key::Down, "DOWN"),
+    (glfw::Key::Space, "SPACE"), (glfw::Key::Enter, "ENTER"), (glfw::Key::Escape, "ESCAPE"), (glfw::Key::Tab, "TAB"),
+    (glfw::Key::Backspace, "BACKSPACE"),
+    (glfw::Key::LeftShift, "LSHIFT"), (glfw::Key::RightShift, "RSHIFT"),
+    (glfw::Key::LeftControl, "LCTRL"), (glfw::Key::RightControl, "RCTRL"),
+    (glfw::Key::LeftAlt, "LALT"), (glfw::Key::RightAlt, "RALT"),
+    (glfw::Key::Comma, ","), (glfw::Key::Period, "."), (glfw::Key::Slash, "/"), (glfw::Key::Semicolon, ";"),
+    (glfw::Key::Minus, "-"), (glfw::Key::Equal, "="),
+];
+
+pub fn get_key_name(key: glfw::Key) -> Option<&'static str> {
+    KEY_NAMES.iter().find(|(known, _)| *known == key).map(|(_, name)| *name)
+}
+
+pub fn parse_key(name: &str) -> Option<glfw::Key> {
+    KEY_NAMES.iter().find(|(_, known)| *known == name).map(|(key, _)| *key)
+}
+
+type BindingTable = HashMap<Action, Vec<glfw::Key>>;
+
+// Which keys trigger which action, any number of keys per action and the same key may trigger several
+pub struct Bindings {
+    path: String,
+    defaults: BindingTable,
+    table: BindingTable,
+}
+
+fn parse_table(names: &BTreeMap<Action, Vec<String>>) -> BindingTable {
+    names
+        .iter()
+        .map(|(action, keys)| {
+            let keys = keys
+                .iter()
+                .map(|name| parse_key(name).unwrap_or_else(|| panic!("Unknown key {} bound to {:?}", name, action)))
+                .collect();
+
+            (*action, keys)
+        })
+        .collect()
+}
+
+impl Bindings {
+    pub fn load(path: &str) -> Bindings {
+        let config = PersistedConfig::<BTreeMap<Action, Vec<String>>>::load(path, "controls");
+        let defaults = parse_table(&config.defaults);
+
+        // actions missing from the saved bindings (like ones added since) keep their defaults
+        let mut table = defaults.clone();
+
+        if let Some(saved) = config.load_saved() {
+            table.extend(parse_table(&saved));
+        }
+
+        Bindings { path: config.path, defaults, table }
+    }
+
+    pub fn save(&self) {
+        let names: BTreeMap<Action, Vec<&str>> = self
+            .table
+            .iter()
+            .map(|(action, keys)| (*action, keys.iter().filter_map(|key| get_key_name(*key)).collect()))
+            .collect();
+
+        save_settings(&self.path, &names, "controls");
+    }
+
+    // the actions of every key pressed this frame, in the order the keys were pressed
+    pub fn get_actions(&self, events: &[glfw::WindowEvent]) -> Vec<Action> {
+        let mut actions = Vec::new();
+
+        for event in events {
+            let glfw::WindowEvent::Key(key, _, glfw::Action::Press, _) = *event else {
+                continue;
+            };
+
+            actions.extend(Action::ALL.iter().filter(|action| self.get_keys(**action).contains(&key)));
+        }
+
+        actions
+    }
+
+    pub fn get_keys(&self, action: Action) -> &[glfw::Key] {
+        self.table.get(&action).map(|keys| keys.as_slice()).unwrap_or(&[])
+    }
+
+    pub fn add_key(&mut self, action: Action, key: glfw::Key) {
+        let keys = self.table.entry(action).or_default();
+
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    pub fn clear(&mut self, action: Action) {
+        self.table.insert(action, Vec::new());
+    }
+
+    pub fn reset(&mut self) {
+        self.table = self.defaults.clone();
+    }
+}
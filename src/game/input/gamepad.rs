@@ -0,0 +1,240 @@
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs};
+
+use super::Action;
+
+const JOYSTICKS: [glfw::JoystickId; 16] = [
+    glfw::JoystickId::Joystick1, glfw::JoystickId::Joystick2, glfw::JoystickId::Joystick3, glfw::JoystickId::Joystick4,
+    glfw::JoystickId::Joystick5, glfw::JoystickId::Joystick6, glfw::JoystickId::Joystick7, glfw::JoystickId::Joystick8,
+    glfw::JoystickId::Joystick9, glfw::JoystickId::Joystick10, glfw::JoystickId::Joystick11, glfw::JoystickId::Joystick12,
+    glfw::JoystickId::Joystick13, glfw::JoystickId::Joystick14, glfw::JoystickId::Joystick15, glfw::JoystickId::Joystick16,
+];
+
+// Buttons as GLFW's standard gamepad mapping lays them out, plus the left stick pushed past the deadzone
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+pub enum PadInput {
+    A,
+    B,
+    X,
+    Y,
+    LeftBumper,
+    RightBumper,
+    Back,
+    Start,
+    DpadUp,
+    DpadDown,
+    DpadLeft,
+    DpadRight,
+    StickUp,
+    StickDown,
+    StickLeft,
+    StickRight,
+}
+
+const BUTTONS: [(glfw::GamepadButton, PadInput); 12] = [
+    (glfw::GamepadButton::ButtonA, PadInput::A),
+    (glfw::GamepadButton::ButtonB, PadInput::B),
+    (glfw::GamepadButton::ButtonX, PadInput::X),
+    (glfw::GamepadButton::ButtonY, PadInput::Y),
+    (glfw::GamepadButton::ButtonLeftBumper, PadInput::LeftBumper),
+    (glfw::GamepadButton::ButtonRightBumper, PadInput::RightBumper),
+    (glfw::GamepadButton::ButtonBack, PadInput::Back),
+    (glfw::GamepadButton::ButtonStart, PadInput::Start),
+    (glfw::GamepadButton::ButtonDpadUp, PadInput::DpadUp),
+    (glfw::GamepadButton::ButtonDpadDown, PadInput::DpadDown),
+    (glfw::GamepadButton::ButtonDpadLeft, PadInput::DpadLeft),
+    (glfw::GamepadButton::ButtonDpadRight, PadInput::DpadRight),
+];
+
+// One reading of a controller, taken from GLFW or made up to drive the game without one
+#[derive(Clone, Debug, Default)]
+pub struct PadState {
+    // only the buttons being held, the stick directions are worked out from the axes
+    pub buttons: Vec<PadInput>,
+    // from -1 to 1, y points down like GLFW's
+    pub stick: (f32, f32),
+}
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    pub enabled: bool,
+    // how far the stick has to be pushed before it counts, from 0 to 1
+    pub deadzone: f32,
+    pub bindings: BTreeMap<Action, Vec<PadInput>>,
+}
+
+// Turns the first connected gamepad into the same actions the keyboard produces, one per button press.
+// Controllers can be plugged in and out at any time, the next one found takes over.
+pub struct Gamepad {
+    config: Config,
+
+    joystick: Option<glfw::JoystickId>,
+    held: Vec<PadInput>,
+}
+
+impl Gamepad {
+    fn load_config(path: &str) -> Config {
+        let contents = fs::read_to_string(path).expect("Failed to read the gamepad config file");
+
+        serde_json::from_str(&contents).expect("Could not parse gamepad JSON config")
+    }
+
+    pub fn from_config(path: &str) -> Option<Gamepad> {
+        let config = Gamepad::load_config(path);
+
+        if !config.enabled {
+            return None;
+        }
+
+        Some(Gamepad { config, joystick: None, held: Vec::new() })
+    }
+
+    fn find_joystick(&mut self, glfw: &glfw::Glfw) -> Option<glfw::JoystickId> {
+        if let Some(id) = self.joystick {
+            if glfw.get_joystick(id).is_gamepad() {
+                return Some(id);
+            }
+
+            println!("Gamepad {:?} disconnected", id);
+            self.joystick = None;
+        }
+
+        let id = JOYSTICKS.into_iter().find(|id| glfw.get_joystick(*id).is_gamepad())?;
+        let name = glfw.get_joystick(id).get_gamepad_name().unwrap_or_default();
+
+        println!("Gamepad {:?} connected: {}", id, name);
+        self.joystick = Some(id);
+
+        Some(id)
+    }
+
+    fn read_state(glfw: &glfw::Glfw, id: glfw::JoystickId) -> Option<PadState> {
+        let state = glfw.get_joystick(id).get_gamepad_state()?;
+
+        Some(PadState {
+            buttons: BUTTONS
+                .iter()
+                .filter(|(button, _)| state.get_button_state(*button) == glfw::Action::Press)
+                .map(|(_, input)| *input)
+                .collect(),
+            stick: (state.get_axis(glfw::GamepadAxis::AxisLeftX), state.get_axis(glfw::GamepadAxis::AxisLeftY)),
+        })
+    }
+
+    pub fn poll(&mut self, glfw: &glfw::Glfw) -> Vec<Action> {
+        let state = self.find_joystick(glfw).and_then(|id| Gamepad::read_state(glfw, id));
+
+        self.update(state.as_ref())
+    }
+
+    // only the stick's main direction counts so a sloppy push sideways doesn't also soft drop
+    fn get_stick_input(&self, stick: (f32, f32)) -> Option<PadInput> {
+        let (x, y) = stick;
+
+        if x.abs().max(y.abs()) < self.config.deadzone {
+            return None;
+        }
+
+        Some(match (x.abs() >= y.abs(), x < 0.0, y < 0.0) {
+            (true, true, _) => PadInput::StickLeft,
+            (true, false, _) => PadInput::StickRight,
+            (false, _, true) => PadInput::StickUp,
+            (false, _, false) => PadInput::StickDown,
+        })
+    }
+
+    // the actions of every input that went down since the last state, None is a disconnected pad with nothing held
+    pub fn update(&mut self, state: Option<&PadState>) -> Vec<Action> {
+        let mut held = Vec::new();
+
+        if let Some(state) = state {
+            held.extend_from_slice(&state.buttons);
+            held.extend(self.get_stick_input(state.stick));
+        }
+
+        let actions = held
+            .iter()
+            .filter(|input| !self.held.contains(input))
+            .flat_map(|input| {
+                Action::ALL.into_iter().filter(|action| {
+                    self.config.bindings.get(action).is_some_and(|inputs| inputs.contains(input))
+                })
+            })
+            .collect();
+
+        self.held = held;
+
+        actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Gamepad, PadInput, PadState};
+    use crate::game::input::Action;
+
+    fn get_gamepad() -> Gamepad {
+        let mut config = Gamepad::load_config("conf/gamepad.json");
+        config.deadzone = 0.5;
+
+        Gamepad { config, joystick: None, held: Vec::new() }
+    }
+
+    fn get_state(buttons: &[PadInput], stick: (f32, f32)) -> PadState {
+        PadState { buttons: buttons.to_vec(), stick }
+    }
+
+    #[test]
+    fn held_buttons_only_act_when_pressed() {
+        let mut gamepad = get_gamepad();
+
+        assert_eq!(gamepad.update(Some(&get_state(&[PadInput::DpadLeft], (0.0, 0.0)))), vec![Action::MoveLeft]);
+        assert_eq!(gamepad.update(Some(&get_state(&[PadInput::DpadLeft], (0.0, 0.0)))), vec![]);
+
+        // a second button going down doesn't repeat the one still held
+        assert_eq!(
+            gamepad.update(Some(&get_state(&[PadInput::DpadLeft, PadInput::DpadUp], (0.0, 0.0)))),
+            vec![Action::HardDrop]
+        );
+
+        gamepad.update(Some(&get_state(&[], (0.0, 0.0))));
+        assert_eq!(gamepad.update(Some(&get_state(&[PadInput::DpadLeft], (0.0, 0.0)))), vec![Action::MoveLeft]);
+    }
+
+    #[test]
+    fn the_stick_is_ignored_inside_the_deadzone() {
+        let mut gamepad = get_gamepad();
+
+        assert_eq!(gamepad.update(Some(&get_state(&[], (-0.4, 0.3)))), vec![]);
+        assert_eq!(gamepad.update(Some(&get_state(&[], (-0.6, 0.3)))), vec![Action::MoveLeft]);
+
+        // letting it drift back inside releases it
+        gamepad.update(Some(&get_state(&[], (-0.4, 0.0))));
+        assert_eq!(gamepad.update(Some(&get_state(&[], (-0.6, 0.0)))), vec![Action::MoveLeft]);
+    }
+
+    #[test]
+    fn the_stick_only_counts_its_dominant_axis() {
+        let mut gamepad = get_gamepad();
+
+        assert_eq!(gamepad.update(Some(&get_state(&[], (0.8, 0.6)))), vec![Action::MoveRight]);
+
+        // moving from mostly sideways to mostly down is a new soft drop
+        assert_eq!(gamepad.update(Some(&get_state(&[], (0.6, 0.8)))), vec![Action::SoftDrop]);
+        assert_eq!(gamepad.update(Some(&get_state(&[], (-0.9, 0.2)))), vec![Action::MoveLeft]);
+    }
+
+    #[test]
+    fn disconnecting_releases_everything() {
+        let mut gamepad = get_gamepad();
+
+        gamepad.update(Some(&get_state(&[PadInput::DpadUp], (0.0, 0.9))));
+        assert_eq!(gamepad.update(None), vec![]);
+
+        // the same inputs on the next controller are fresh presses
+        assert_eq!(
+            gamepad.update(Some(&get_state(&[PadInput::DpadUp], (0.0, 0.9)))),
+            vec![Action::HardDrop, Action::SoftDrop]
+        );
+    }
+}
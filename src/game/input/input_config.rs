@@ -0,0 +1,17 @@
+use serde::Deserialize;
+use std::fs;
+
+// Paths of the input scripts to record the player to and to play back, each new game starts them over
+#[derive(Deserialize, Debug, Clone)]
+pub struct InputConfig {
+    pub record: Option<String>,
+    pub replay: Option<String>,
+}
+
+impl InputConfig {
+    pub fn load(path: &str) -> InputConfig {
+        let contents = fs::read_to_string(path).expect("Failed to read the input config file");
+
+        serde_json::from_str(&contents).expect("Could not parse input JSON config")
+    }
+}
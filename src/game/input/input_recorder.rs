@@ -0,0 +1,34 @@
+use std::{fs::File, io::Write};
+
+use super::Action;
+use crate::game::stats::Stats;
+
+// Writes the moves of a game as an input script, as they happen so nothing is lost if the game is closed.
+// Only the moves are kept since the menus and pausing don't happen in game time, the mode and seed go in the header
+// and the stats of a finished game go at the end.
+pub struct InputRecorder {
+    file: File,
+}
+
+impl InputRecorder {
+    pub fn create(path: &str, mode: &str, seed: u64) -> InputRecorder {
+        let mut file = File::create(path).expect("Failed to create the input recording");
+
+        writeln!(file, "# mode {mode}\n# seed {seed}").expect("Failed to write the input recording");
+
+        InputRecorder { file }
+    }
+
+    pub fn record(&mut self, time: u128, actions: &[Action]) {
+        for action in actions.iter().filter(|action| action.is_piece_move()) {
+            writeln!(self.file, "{time} {action:?}").expect("Failed to write the input recording");
+        }
+    }
+
+    // a comment like the header, so the recording still plays back as a script
+    pub fn finish(mut self, stats: &Stats) {
+        let stats = serde_json::to_string(stats).expect("Failed to serialize the stats");
+
+        writeln!(self.file, "# stats {stats}").expect("Failed to write the input recording");
+    }
+}
@@ -0,0 +1,106 @@
+use std::fs;
+
+use super::{Action, InputSource};
+
+// Actions scheduled by time, one "<milliseconds> <action>" pair per line.
+// Times are counted from the start of the game and lines starting with # are comments,
+// except for "# mode <name>" and "# seed <number>" which say how the game was started.
+pub struct InputScript {
+    events: Vec<(u128, Action)>,
+    next_event: usize,
+
+    mode: Option<String>,
+    seed: Option<u64>,
+}
+
+// scripts written before actions existed name the default keys instead
+fn parse_action(name: &str) -> Option<Action> {
+    match name {
+        "Left" => Some(Action::MoveLeft),
+        "Right" => Some(Action::MoveRight),
+        "Down" => Some(Action::SoftDrop),
+        "Space" => Some(Action::HardDrop),
+        "R" => Some(Action::RotateCW),
+        "N" => Some(Action::Restart),
+        "B" => Some(Action::ToggleBot),
+        _ => serde_json::from_value(serde_json::Value::String(name.to_string())).ok(),
+    }
+}
+
+impl InputScript {
+    pub fn load(path: &str) -> InputScript {
+        let contents = fs::read_to_string(path).expect("Failed to read the input script");
+
+        InputScript::parse(&contents)
+    }
+
+    pub fn parse(contents: &str) -> InputScript {
+        let mut events = Vec::<(u128, Action)>::new();
+        let mut mode = None;
+        let mut seed = None;
+
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+
+            if let Some(comment) = line.strip_prefix('#') {
+                match comment.split_whitespace().collect::<Vec<_>>()[..] {
+                    ["mode", name] => mode = Some(name.to_string()),
+                    ["seed", value] => {
+                        seed = Some(value.parse().unwrap_or_else(|_| panic!("Invalid seed on line {} of the input script", i + 1)));
+                    }
+                    _ => (),
+                }
+
+                continue;
+            }
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+
+            let time = parts
+                .next()
+                .and_then(|time| time.parse::<u128>().ok())
+                .unwrap_or_else(|| panic!("Invalid time on line {} of the input script", i + 1));
+
+            let action = parts
+                .next()
+                .and_then(parse_action)
+                .unwrap_or_else(|| panic!("Unknown action on line {} of the input script", i + 1));
+
+            events.push((time, action));
+        }
+
+        events.sort_by_key(|event| event.0);
+
+        InputScript { events, next_event: 0, mode, seed }
+    }
+
+    pub fn get_mode(&self) -> Option<&str> {
+        self.mode.as_deref()
+    }
+
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+}
+
+impl InputSource for InputScript {
+    // Every action due up to and including `time` that hasn't been handed out yet
+    fn poll(&mut self, time: u128) -> Vec<Action> {
+        let mut actions = Vec::new();
+
+        while self.next_event < self.events.len() && self.events[self.next_event].0 <= time {
+            actions.push(self.events[self.next_event].1);
+            self.next_event += 1;
+        }
+
+        actions
+    }
+
+    fn is_finished(&self) -> bool {
+        self.next_event >= self.events.len()
+    }
+}
@@ -0,0 +1,11 @@
+use super::Action;
+
+// Anything the board can take actions from besides the player, asked once per update with the time since the game started
+pub trait InputSource {
+    fn poll(&mut self, time: u128) -> Vec<Action>;
+
+    // whether it has nothing left to hand out
+    fn is_finished(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,20 @@
+mod action;
+pub use action::Action;
+
+mod bindings;
+pub use bindings::{get_key_name, Bindings};
+
+mod gamepad;
+pub use gamepad::Gamepad;
+
+mod input_config;
+pub use input_config::InputConfig;
+
+mod input_source;
+pub use input_source::InputSource;
+
+mod input_script;
+pub use input_script::InputScript;
+
+mod input_recorder;
+pub use input_recorder::InputRecorder;
@@ -1,6 +1,9 @@
 mod board;
 use board::Board;
+pub use board::GameMode;
 pub use board::GameState;
+pub use board::Playfield;
+pub use board::Skin;
 
 mod user_interface;
 pub use user_interface::UserInterface;
@@ -9,9 +12,26 @@ mod game;
 pub use game::Game;
 
 mod audio;
-pub use audio::AudioManager;
+pub use audio::{AudioBackend, AudioManager, Cue, NullBackend, RecordingBackend, SoundEvent};
 pub use audio::Sound;
 
 mod text;
 
-mod button;
\ No newline at end of file
+mod button;
+
+mod spectator;
+use spectator::{BoardSnapshot, SpectatorServer};
+
+mod bot;
+pub use bot::{Bot, Controller, TbpBot};
+
+mod stats;
+
+mod scene;
+
+mod particles;
+
+mod settings;
+
+mod input;
+pub use input::{InputScript, InputSource};
\ No newline at end of file
@@ -0,0 +1,2 @@
+mod particle_system;
+pub use particle_system::ParticleSystem;
\ No newline at end of file
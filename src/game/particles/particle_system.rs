@@ -0,0 +1,320 @@
+use ash::vk;
+use rand::{prelude::*, rngs::ThreadRng};
+use serde::Deserialize;
+use std::fs;
+
+use crate::{types::*, *};
+use crate::game::board::{BoardEvent, GameState};
+
+// position, size and colour
+const PARTICLE_SIZE: usize = 16;
+
+// Where an emitter takes the colour of its particles from
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub enum ColorSource {
+    Block,
+    Fixed([u8; 4]),
+}
+
+// Distances are in cells and times in milliseconds, the ranges are picked from uniformly for every particle
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct EmitterConfig {
+    pub count: u32,
+    pub lifetime: (u32, u32),
+    pub speed: (f32, f32),
+    pub size: (f32, f32),
+    pub gravity: f32,
+    pub color: ColorSource,
+}
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    pub enabled: bool,
+    pub max_particles: usize,
+
+    // emitted for every block of a cleared row
+    pub line_clear: EmitterConfig,
+    // emitted for every cell a hard dropped piece travelled through
+    pub hard_drop: EmitterConfig,
+}
+
+struct Particle {
+    pos: (f32, f32),
+    velocity: (f32, f32),
+    gravity: f32,
+    size: f32,
+    color: [u8; 4],
+
+    age: u32,
+    lifetime: u32,
+}
+
+// Block fragments simulated on the CPU and drawn instanced in their own subpass over the board
+pub struct ParticleSystem {
+    config: Config,
+    particles: Vec<Particle>,
+    rng: ThreadRng,
+    // particles live in board cells, this is how many cells on screen one of them covers
+    scale: f32,
+
+    previous_update: u128,
+
+    instance_buffer: Option<Buffer>,
+    instance_count: u32,
+
+    transfer_command_buffer: CommandBuffer,
+    fence: Fence,
+}
+
+impl<'a> ParticleSystem {
+    fn load_config(path: &str) -> Config {
+        let contents = fs::read_to_string(path).expect("Failed to read the particle config file");
+
+        serde_json::from_str(&contents).expect("Could not parse particle JSON config")
+    }
+
+    pub fn from_config(device: &Device, command_pool: &CommandPool, path: &str) -> Option<ParticleSystem> {
+        let config = ParticleSystem::load_config(path);
+
+        if !config.enabled {
+            return None;
+        }
+
+        Some(ParticleSystem {
+            particles: Vec::with_capacity(config.max_particles),
+            config,
+            rng: rand::rng(),
+            scale: 1.0,
+            previous_update: 0,
+            instance_buffer: None,
+            instance_count: 0,
+            transfer_command_buffer: CommandBuffer::new(device, command_pool, false),
+            fence: Fence::new(device, false),
+        })
+    }
+
+    fn emit(&mut self, emitter: EmitterConfig, pos: (f32, f32), block_color: [u8; 4]) {
+        for _ in 0..emitter.count {
+            if self.particles.len() >= self.config.max_particles {
+                return;
+            }
+
+            let angle = self.rng.random_range(0.0..std::f32::consts::TAU);
+            let speed = self.rng.random_range(emitter.speed.0..=emitter.speed.1);
+
+            self.particles.push(Particle {
+                pos: (pos.0 + self.rng.random_range(-0.5..=0.5), pos.1 + self.rng.random_range(-0.5..=0.5)),
+                velocity: (angle.cos() * speed, angle.sin() * speed),
+                gravity: emitter.gravity,
+                size: self.rng.random_range(emitter.size.0..=emitter.size.1),
+                color: match emitter.color {
+                    ColorSource::Block => block_color,
+                    ColorSource::Fixed(color) => color,
+                },
+                age: 0,
+                lifetime: self.rng.random_range(emitter.lifetime.0..=emitter.lifetime.1).max(1),
+            });
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &BoardEvent) {
+        match event {
+            BoardEvent::LinesCleared { rows, .. } => {
+                for row in rows {
+                    for (x, block) in row.blocks.iter().enumerate() {
+                        if *block == WALL_BLOCK {
+                            continue;
+                        }
+
+                        self.emit(self.config.line_clear, (x as f32 + 0.5, row.y as f32 + 0.5), *block);
+                    }
+                }
+            }
+
+            // a trail through every cell above the blocks the piece landed on
+            BoardEvent::HardDropped { tetromino, distance } => {
+                for pos in tetromino.get_data().chunks(2) {
+                    for step in 1..=*distance {
+                        let y = pos[1] as f32 - step as f32;
+
+                        if y >= 0.0 {
+                            self.emit(self.config.hard_drop, (pos[0] as f32 + 0.5, y + 0.5), tetromino.get_color());
+                        }
+                    }
+                }
+            }
+
+            BoardEvent::GameReset => self.particles.clear(),
+
+            _ => (),
+        }
+    }
+
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    // the particles hang in the air while the game is paused
+    pub fn update(&mut self, curr: u128, state: GameState) {
+        let elapsed = curr.saturating_sub(self.previous_update).min(100) as u32;
+        self.previous_update = curr;
+
+        if matches!(state, GameState::PAUSED) {
+            return;
+        }
+
+        let dt = elapsed as f32 / 1000.0;
+
+        for particle in self.particles.iter_mut() {
+            particle.velocity.1 += particle.gravity * dt;
+
+            particle.pos.0 += particle.velocity.0 * dt;
+            particle.pos.1 += particle.velocity.1 * dt;
+
+            particle.age += elapsed;
+        }
+
+        self.particles.retain(|particle| particle.age < particle.lifetime);
+    }
+
+    // only the live particles, which is all the draw reads
+    fn get_instance_data(&self) -> Vec<u8> {
+        let mut data = Vec::<u8>::with_capacity(self.particles.len() * PARTICLE_SIZE);
+
+        for particle in &self.particles {
+            let life = 1.0 - particle.age as f32 / particle.lifetime as f32;
+
+            data.extend_from_slice(&[
+                (particle.pos.0 * self.scale).to_ne_bytes(),
+                (particle.pos.1 * self.scale).to_ne_bytes(),
+                (particle.size * life.sqrt() * self.scale).to_ne_bytes(),
+                [particle.color[0], particle.color[1], particle.color[2], (particle.color[3] as f32 * life) as u8],
+            ].concat());
+        }
+
+        data
+    }
+
+    fn handle_transfer(&mut self, device: &Device, data: &[u8]) {
+        self.transfer_command_buffer.begin(device, &vk::CommandBufferInheritanceInfo::default(), vk::CommandBufferUsageFlags::empty());
+
+        // the buffer is made with room for every particle so it never has to be recreated, after that only the used part is uploaded
+        if let Some(buff) = &mut self.instance_buffer {
+            buff.update(device, &mut self.transfer_command_buffer, data);
+        } else {
+            let mut full = data.to_vec();
+            full.resize(self.config.max_particles.max(1) * PARTICLE_SIZE, 0);
+
+            self.instance_buffer = Some(Buffer::new(device, &mut self.transfer_command_buffer, &full, BufferType::Vertex, true));
+        }
+
+        self.transfer_command_buffer.end(device);
+
+        CommandBuffer::submit(device, &[self.transfer_command_buffer.get_command_buffer()], &[], &[], self.fence.get_fence());
+
+        unsafe {
+            device.get_ash_device().wait_for_fences(&[self.fence.get_fence()], true, u64::MAX)
+                .expect("Failed to wait for the particle transfer fence");
+            device.get_ash_device().reset_fences(&[self.fence.get_fence()])
+                .expect("Failed to reset the particle transfer fence");
+        }
+
+        self.transfer_command_buffer.cleanup(device);
+    }
+
+    pub fn draw(&mut self, device: &Device, render_pass: &RenderPass, command_buffer: &CommandBuffer, vertex_buffer: &Buffer, index_buffer: &Buffer,
+     subpass_index: u32) {
+        self.instance_count = self.particles.len() as u32;
+
+        if self.instance_count == 0 {
+            return;
+        }
+
+        let data = self.get_instance_data();
+        self.handle_transfer(device, &data);
+
+        unsafe {
+            device.get_ash_device().cmd_bind_pipeline(command_buffer.get_command_buffer(),
+             vk::PipelineBindPoint::GRAPHICS, render_pass.get_pipeline(subpass_index as usize));
+
+            device.get_ash_device().cmd_bind_vertex_buffers(
+                command_buffer.get_command_buffer(), 0,
+                 &[vertex_buffer.get_buffer(), self.instance_buffer.as_ref().unwrap().get_buffer()], &[0, 0]);
+
+            device.get_ash_device().cmd_bind_index_buffer(command_buffer.get_command_buffer(), index_buffer.get_buffer(), 0, vk::IndexType::UINT16);
+
+            device.get_ash_device().cmd_draw_indexed(command_buffer.get_command_buffer(), 6, self.instance_count,
+             0, 0, 0);
+        }
+    }
+
+    pub fn get_required_vertex_input_states() -> ([vk::PipelineVertexInputStateCreateInfo<'a>; 1], VertexInputData) {
+        let vertex_bindings = vec![
+            vk::VertexInputBindingDescription {
+                binding: 0,
+                stride: 8,
+                input_rate: vk::VertexInputRate::VERTEX,
+            },
+
+            vk::VertexInputBindingDescription {
+                binding: 1,
+                stride: PARTICLE_SIZE as u32,
+                input_rate: vk::VertexInputRate::INSTANCE,
+            },
+        ];
+
+        let vertex_attributes = vec![
+            vk::VertexInputAttributeDescription {
+                location: 0,
+                binding: 0,
+
+                format: vk::Format::R32G32_SFLOAT,
+                offset: 0,
+            },
+
+            vk::VertexInputAttributeDescription {
+                location: 1,
+                binding: 1,
+
+                format: vk::Format::R32G32_SFLOAT,
+                offset: 0,
+            },
+
+            vk::VertexInputAttributeDescription {
+                location: 2,
+                binding: 1,
+
+                format: vk::Format::R32_SFLOAT,
+                offset: 8,
+            },
+
+            vk::VertexInputAttributeDescription {
+                location: 3,
+                binding: 1,
+
+                format: vk::Format::R8G8B8A8_UNORM,
+                offset: 12,
+            },
+        ];
+
+        ([
+            vk::PipelineVertexInputStateCreateInfo {
+                s_type: vk::StructureType::PIPELINE_VERTEX_INPUT_STATE_CREATE_INFO,
+
+                vertex_attribute_description_count: vertex_attributes.len() as u32,
+                p_vertex_attribute_descriptions: vertex_attributes.as_ptr(),
+
+                vertex_binding_description_count: vertex_bindings.len() as u32,
+                p_vertex_binding_descriptions: vertex_bindings.as_ptr(),
+
+                ..Default::default()
+            },
+        ], (vertex_bindings, vertex_attributes))
+    }
+
+    pub fn destroy(&mut self, device: &Device) {
+        if let Some(buff) = &mut self.instance_buffer {
+            buff.destroy(device);
+        }
+    }
+}
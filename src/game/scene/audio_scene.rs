@@ -0,0 +1,99 @@
+use crate::types::*;
+use crate::game::{input::Action, settings::{AudioSettings, Volume}, user_interface::Menu};
+
+use super::*;
+
+// each press turns a volume up or down by this much
+const VOLUME_STEP: u32 = 10;
+
+// A row of volume down, volume up and mute buttons for every bus, the game applies and saves the settings once the scene has changed them
+pub struct AudioScene {
+    menu: Option<Menu>,
+}
+
+impl AudioScene {
+    pub fn new() -> AudioScene {
+        AudioScene { menu: None }
+    }
+
+    // in the same order as the rows on screen
+    fn get_volumes(settings: &mut AudioSettings) -> [(&'static str, &mut Volume); 4] {
+        [
+            ("MASTER", &mut settings.master),
+            ("MUSIC", &mut settings.music),
+            ("SFX", &mut settings.sfx),
+            ("UI", &mut settings.ui),
+        ]
+    }
+
+    fn open_menu(&mut self, context: &mut SceneContext) {
+        context.close_menu(&mut self.menu);
+
+        let rows: Vec<(Rect, Rect, String, Rect, &str)> = AudioScene::get_volumes(context.audio_settings)
+            .iter()
+            .zip(0u32..)
+            .map(|((name, volume), i)| (
+                Rect{ x: 40, y: 400 + i * 150, width: 100, height: 100 },
+                Rect{ x: 160, y: 400 + i * 150, width: 340, height: 100 },
+                format!("{}:{}", name, volume.level),
+                Rect{ x: 520, y: 400 + i * 150, width: 160, height: 100 },
+                if volume.muted { "OFF" } else { "ON" },
+            ))
+            .collect();
+
+        let back_rect = Rect{ x: 210, y: 1050, width: 300, height: 100 };
+
+        let mut buttons: Vec<(&Rect, Color, &str)> = Vec::new();
+
+        for (lower_rect, level_rect, level, mute_rect, mute) in &rows {
+            buttons.push((lower_rect, (255, 255, 255), "-"));
+            buttons.push((level_rect, (255, 255, 255), level.as_str()));
+            buttons.push((mute_rect, (255, 255, 255), mute));
+        }
+
+        buttons.push((&back_rect, (255, 255, 255), "BACK"));
+
+        self.menu = Some(context.open_menu(&[("AUDIO", &Rect{ x: 210, y: 150, width: 300, height: 150 })], &buttons));
+    }
+}
+
+impl Scene for AudioScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        self.open_menu(context);
+    }
+
+    fn update(&mut self, context: &mut SceneContext) -> Transition {
+        if context.is_pressed(Action::Back) {
+            return Transition::Switch(Box::new(TitleScene::new()));
+        }
+
+        let Some(i) = context.handle_menu(&self.menu) else {
+            return Transition::Stay;
+        };
+
+        // three buttons per volume, down, the level which turns it up, then the mute
+        let mut volumes = AudioScene::get_volumes(context.audio_settings);
+
+        let Some((_, volume)) = volumes.get_mut(i / 3) else {
+            return Transition::Switch(Box::new(TitleScene::new()));
+        };
+
+        match i % 3 {
+            0 => volume.lower(VOLUME_STEP),
+            1 => volume.raise(VOLUME_STEP),
+            _ => volume.muted = !volume.muted,
+        }
+
+        self.open_menu(context);
+
+        Transition::Stay
+    }
+
+    fn draw(&self, context: &mut DrawContext) {
+        context.draw_interface(false, self.menu.as_ref());
+    }
+
+    fn exit(&mut self, context: &mut SceneContext) {
+        context.close_menu(&mut self.menu);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::types::*;
+use crate::game::{input::{get_key_name, Action}, user_interface::Menu};
+
+use super::*;
+
+// Lists every action with the keys bound to it. Clicking an action waits for a key to add to it,
+// escape cancels and backspace unbinds every key from it. Changes are saved as soon as they're made.
+pub struct ControlsScene {
+    menu: Option<Menu>,
+
+    rebinding: Option<Action>,
+}
+
+impl ControlsScene {
+    pub fn new() -> ControlsScene {
+        ControlsScene { menu: None, rebinding: None }
+    }
+
+    fn get_binding_text(context: &SceneContext, action: Action) -> String {
+        let names: Vec<&str> = context.bindings.get_keys(action).iter().filter_map(|key| get_key_name(*key)).collect();
+
+        if names.is_empty() {
+            return String::from("NONE");
+        }
+
+        names.join(",")
+    }
+
+    // the title is the first text, the bindings follow in the same order as the actions
+    fn set_binding_text(&mut self, context: &mut SceneContext, action: Action, text: &str) {
+        let Some(menu) = self.menu.as_mut() else {
+            return;
+        };
+
+        let index = Action::ALL.iter().position(|known| *known == action).unwrap_or_default() + 1;
+
+        context.user_interface.change_menu_text(context.device, menu, index, text);
+    }
+
+    fn handle_rebinding(&mut self, context: &mut SceneContext, action: Action) {
+        let Some(key) = context.get_pressed_keys().into_iter().next() else {
+            return;
+        };
+
+        match key {
+            glfw::Key::Escape => (),
+            glfw::Key::Backspace => context.bindings.clear(action),
+            key if get_key_name(key).is_some() => context.bindings.add_key(action, key),
+            // keys that have no name can't be saved, so they can't be bound either
+            _ => return,
+        }
+
+        context.bindings.save();
+        self.rebinding = None;
+
+        let text = ControlsScene::get_binding_text(context, action);
+        self.set_binding_text(context, action, &text);
+    }
+}
+
+impl Scene for ControlsScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        let rows: Vec<(Rect, Rect)> = (0..Action::ALL.len() as u32)
+            .map(|i| (
+                Rect{ x: 60, y: 170 + i * 88, width: 280, height: 70 },
+                Rect{ x: 380, y: 175 + i * 88, width: 280, height: 60 },
+            ))
+            .collect();
+
+        let binding_texts: Vec<String> = Action::ALL.iter().map(|action| ControlsScene::get_binding_text(context, *action)).collect();
+
+        let title_rect = Rect{ x: 210, y: 30, width: 300, height: 110 };
+        let mut texts: Vec<(&str, &Rect)> = vec![("CONTROLS", &title_rect)];
+        texts.extend(binding_texts.iter().map(|text| text.as_str()).zip(rows.iter().map(|row| &row.1)));
+
+        let defaults_rect = Rect{ x: 60, y: 1150, width: 280, height: 90 };
+        let back_rect = Rect{ x: 380, y: 1150, width: 280, height: 90 };
+
+        let mut buttons: Vec<(&Rect, Color, &str)> = Action::ALL
+            .iter()
+            .zip(rows.iter())
+            .map(|(action, row)| (&row.0, (255, 255, 255), action.get_name()))
+            .collect();
+
+        buttons.push((&defaults_rect, (255, 255, 255), "DEFAULTS"));
+        buttons.push((&back_rect, (255, 255, 255), "BACK"));
+
+        self.menu = Some(context.open_menu(&texts, &buttons));
+    }
+
+    fn update(&mut self, context: &mut SceneContext) -> Transition {
+        // every key goes to the action being rebound, including the ones bound to menu actions
+        if let Some(action) = self.rebinding {
+            self.handle_rebinding(context, action);
+            return Transition::Stay;
+        }
+
+        if context.is_pressed(Action::Back) {
+            return Transition::Switch(Box::new(TitleScene::new()));
+        }
+
+        match context.handle_menu(&self.menu) {
+            Some(i) if i < Action::ALL.len() => {
+                self.rebinding = Some(Action::ALL[i]);
+                self.set_binding_text(context, Action::ALL[i], "PRESS-KEY");
+
+                Transition::Stay
+            }
+
+            Some(i) if i == Action::ALL.len() => {
+                context.bindings.reset();
+                context.bindings.save();
+
+                for action in Action::ALL {
+                    let text = ControlsScene::get_binding_text(context, action);
+                    self.set_binding_text(context, action, &text);
+                }
+
+                Transition::Stay
+            }
+
+            Some(_) => Transition::Switch(Box::new(TitleScene::new())),
+            None => Transition::Stay,
+        }
+    }
+
+    fn draw(&self, context: &mut DrawContext) {
+        context.draw_interface(false, self.menu.as_ref());
+    }
+
+    fn exit(&mut self, context: &mut SceneContext) {
+        context.close_menu(&mut self.menu);
+    }
+}
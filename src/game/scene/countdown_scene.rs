@@ -0,0 +1,78 @@
+use crate::types::*;
+use crate::game::{board::GameMode, user_interface::Menu};
+
+use super::*;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Cue {
+    Ready,
+    Go,
+}
+
+impl Cue {
+    fn get_text(&self) -> &'static str {
+        match self {
+            Cue::Ready => "READY",
+            Cue::Go => "GO",
+        }
+    }
+}
+
+// A fresh board that doesn't move until READY and GO have both been shown, their lengths come from the timing config
+pub struct CountdownScene {
+    mode: GameMode,
+    start: u128,
+    cue: Cue,
+
+    menu: Option<Menu>,
+}
+
+impl CountdownScene {
+    pub fn new(mode: GameMode) -> CountdownScene {
+        CountdownScene { mode, start: 0, cue: Cue::Ready, menu: None }
+    }
+
+    // the cues differ in length so each gets its own menu rather than resizing one text
+    fn show_cue(&mut self, context: &mut SceneContext, cue: Cue) {
+        self.cue = cue;
+
+        context.close_menu(&mut self.menu);
+        self.menu = Some(context.open_menu(
+            &[(cue.get_text(), &Rect{ x: 250 - 30 * cue.get_text().len() as u32, y: 300, width: 60 * cue.get_text().len() as u32, height: 150 })],
+            &[],
+        ));
+    }
+}
+
+impl Scene for CountdownScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        context.board.start_game(self.mode);
+        self.start = context.curr;
+
+        self.show_cue(context, Cue::Ready);
+    }
+
+    fn update(&mut self, context: &mut SceneContext) -> Transition {
+        let timing = context.board.get_timing();
+        let elapsed = context.curr - self.start;
+
+        if elapsed >= (timing.ready_time + timing.go_time) as u128 {
+            return Transition::Switch(Box::new(PlayingScene::new(self.mode)));
+        }
+
+        if elapsed >= timing.ready_time as u128 && self.cue == Cue::Ready {
+            self.show_cue(context, Cue::Go);
+        }
+
+        Transition::Stay
+    }
+
+    fn draw(&self, context: &mut DrawContext) {
+        context.draw_interface(true, self.menu.as_ref());
+        context.draw_board();
+    }
+
+    fn exit(&mut self, context: &mut SceneContext) {
+        context.close_menu(&mut self.menu);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{types::*, WindowMode};
+use crate::game::{input::Action, user_interface::Menu};
+
+use super::*;
+
+// Cycles through the window settings, the game applies and saves them once the scene has changed them
+pub struct DisplayScene {
+    menu: Option<Menu>,
+}
+
+// window sizes to pick from, the canvas's own portrait ones first and then common landscape ones that get pillarboxed
+const RESOLUTIONS: [(u32, u32); 6] = [(540, 960), (720, 1280), (900, 1600), (1080, 1920), (1280, 720), (1920, 1080)];
+
+fn get_mode_name(mode: WindowMode) -> &'static str {
+    match mode {
+        WindowMode::Windowed => "WINDOWED",
+        WindowMode::Fullscreen => "FULLSCREEN",
+        WindowMode::Borderless => "BORDERLESS",
+    }
+}
+
+impl DisplayScene {
+    pub fn new() -> DisplayScene {
+        DisplayScene { menu: None }
+    }
+
+    fn open_menu(&mut self, context: &mut SceneContext) {
+        context.close_menu(&mut self.menu);
+
+        let settings = &context.window_settings;
+
+        let mode = format!("MODE:{}", get_mode_name(settings.mode));
+        let size = format!("SIZE:{}X{}", settings.width, settings.height);
+        let monitor = format!("MONITOR:{}", settings.monitor + 1);
+        let vsync = format!("VSYNC:{}", if settings.vsync { "ON" } else { "OFF" });
+
+        self.menu = Some(context.open_menu(
+            &[("DISPLAY", &Rect{ x: 210, y: 200, width: 300, height: 150 })],
+            &[
+                (&Rect{ x: 160, y: 400, width: 400, height: 100 }, (255, 255, 255), mode.as_str()),
+                (&Rect{ x: 160, y: 530, width: 400, height: 100 }, (255, 255, 255), size.as_str()),
+                (&Rect{ x: 160, y: 660, width: 400, height: 100 }, (255, 255, 255), monitor.as_str()),
+                (&Rect{ x: 160, y: 790, width: 400, height: 100 }, (255, 255, 255), vsync.as_str()),
+                (&Rect{ x: 210, y: 950, width: 300, height: 100 }, (255, 255, 255), "BACK"),
+            ],
+        ));
+    }
+}
+
+impl Scene for DisplayScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        self.open_menu(context);
+    }
+
+    fn update(&mut self, context: &mut SceneContext) -> Transition {
+        if context.is_pressed(Action::Back) {
+            return Transition::Switch(Box::new(TitleScene::new()));
+        }
+
+        match context.handle_menu(&self.menu) {
+            Some(0) => {
+                context.window_settings.mode = match context.window_settings.mode {
+                    WindowMode::Windowed => WindowMode::Fullscreen,
+                    WindowMode::Fullscreen => WindowMode::Borderless,
+                    WindowMode::Borderless => WindowMode::Windowed,
+                };
+            }
+
+            // a size that isn't one of the presets starts over from the first
+            Some(1) => {
+                let settings = &mut context.window_settings;
+                let next = RESOLUTIONS.iter().position(|size| *size == (settings.width, settings.height)).map_or(0, |i| (i + 1) % RESOLUTIONS.len());
+
+                (settings.width, settings.height) = RESOLUTIONS[next];
+            }
+
+            Some(2) => {
+                let monitor_count = context.window.get_monitor_count().max(1);
+                context.window_settings.monitor = (context.window_settings.monitor + 1) % monitor_count;
+            }
+
+            Some(3) => context.window_settings.vsync = !context.window_settings.vsync,
+
+            Some(_) => return Transition::Switch(Box::new(TitleScene::new())),
+            None => return Transition::Stay,
+        }
+
+        self.open_menu(context);
+
+        Transition::Stay
+    }
+
+    fn draw(&self, context: &mut DrawContext) {
+        context.draw_interface(false, self.menu.as_ref());
+    }
+
+    fn exit(&mut self, context: &mut SceneContext) {
+        context.close_menu(&mut self.menu);
+    }
+}
@@ -0,0 +1,29 @@
+mod scene;
+pub use scene::{DrawContext, GameResults, Scene, SceneContext, Transition};
+
+mod title_scene;
+pub use title_scene::TitleScene;
+
+mod controls_scene;
+pub use controls_scene::ControlsScene;
+
+mod display_scene;
+pub use display_scene::DisplayScene;
+
+mod audio_scene;
+pub use audio_scene::AudioScene;
+
+mod mode_select_scene;
+pub use mode_select_scene::ModeSelectScene;
+
+mod countdown_scene;
+pub use countdown_scene::CountdownScene;
+
+mod playing_scene;
+pub use playing_scene::PlayingScene;
+
+mod paused_scene;
+pub use paused_scene::PausedScene;
+
+mod results_scene;
+pub use results_scene::ResultsScene;
@@ -0,0 +1,51 @@
+use crate::types::*;
+use crate::game::{board::GameMode, input::Action, user_interface::Menu};
+
+use super::*;
+
+pub struct ModeSelectScene {
+    menu: Option<Menu>,
+}
+
+impl ModeSelectScene {
+    pub fn new() -> ModeSelectScene {
+        ModeSelectScene { menu: None }
+    }
+}
+
+impl Scene for ModeSelectScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        self.menu = Some(context.open_menu(
+            &[("MODE", &Rect{ x: 210, y: 120, width: 300, height: 150 })],
+            &[
+                (&Rect{ x: 210, y: 330, width: 300, height: 90 }, (255, 255, 255), GameMode::ALL[0].get_name()),
+                (&Rect{ x: 210, y: 445, width: 300, height: 90 }, (255, 255, 255), GameMode::ALL[1].get_name()),
+                (&Rect{ x: 210, y: 560, width: 300, height: 90 }, (255, 255, 255), GameMode::ALL[2].get_name()),
+                (&Rect{ x: 210, y: 675, width: 300, height: 90 }, (255, 255, 255), GameMode::ALL[3].get_name()),
+                (&Rect{ x: 210, y: 790, width: 300, height: 90 }, (255, 255, 255), GameMode::ALL[4].get_name()),
+                (&Rect{ x: 210, y: 905, width: 300, height: 90 }, (255, 255, 255), GameMode::ALL[5].get_name()),
+                (&Rect{ x: 210, y: 1060, width: 300, height: 90 }, (255, 255, 255), "BACK"),
+            ],
+        ));
+    }
+
+    fn update(&mut self, context: &mut SceneContext) -> Transition {
+        if context.is_pressed(Action::Back) {
+            return Transition::Switch(Box::new(TitleScene::new()));
+        }
+
+        match context.handle_menu(&self.menu) {
+            Some(i) if i < GameMode::ALL.len() => Transition::Switch(Box::new(CountdownScene::new(GameMode::ALL[i]))),
+            Some(_) => Transition::Switch(Box::new(TitleScene::new())),
+            None => Transition::Stay,
+        }
+    }
+
+    fn draw(&self, context: &mut DrawContext) {
+        context.draw_interface(false, self.menu.as_ref());
+    }
+
+    fn exit(&mut self, context: &mut SceneContext) {
+        context.close_menu(&mut self.menu);
+    }
+}
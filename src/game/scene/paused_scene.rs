@@ -0,0 +1,56 @@
+use crate::types::*;
+use crate::game::{board::GameMode, user_interface::Menu, GameState};
+
+use super::*;
+
+// The board stays hidden until the game is resumed
+pub struct PausedScene {
+    mode: GameMode,
+
+    menu: Option<Menu>,
+}
+
+impl PausedScene {
+    pub fn new(mode: GameMode) -> PausedScene {
+        PausedScene { mode, menu: None }
+    }
+}
+
+impl Scene for PausedScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        context.board.pause_game();
+
+        self.menu = Some(context.open_menu(
+            &[("PAUSED", &Rect{ x: 210, y: 200, width: 300, height: 120 })],
+            &[
+                (&Rect{ x: 210, y: 450, width: 300, height: 100 }, (255, 255, 255), "RESUME"),
+                (&Rect{ x: 210, y: 600, width: 300, height: 100 }, (255, 255, 255), "RESTART"),
+                (&Rect{ x: 210, y: 750, width: 300, height: 100 }, (255, 255, 255), "QUIT"),
+            ],
+        ));
+    }
+
+    fn update(&mut self, context: &mut SceneContext) -> Transition {
+        // the board still sees the pause action so it can resume
+        context.board.update(context.actions, context.audio_manager);
+
+        if matches!(context.board.get_game_state(), GameState::RUNNING) {
+            return Transition::Switch(Box::new(PlayingScene::new(self.mode)));
+        }
+
+        match context.handle_menu(&self.menu) {
+            Some(0) => Transition::Switch(Box::new(PlayingScene::new(self.mode))),
+            Some(1) => Transition::Switch(Box::new(CountdownScene::new(self.mode))),
+            Some(2) => Transition::Switch(Box::new(TitleScene::new())),
+            _ => Transition::Stay,
+        }
+    }
+
+    fn draw(&self, context: &mut DrawContext) {
+        context.draw_interface(false, self.menu.as_ref());
+    }
+
+    fn exit(&mut self, context: &mut SceneContext) {
+        context.close_menu(&mut self.menu);
+    }
+}
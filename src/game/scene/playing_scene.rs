@@ -0,0 +1,54 @@
+use crate::game::{board::GameMode, input::Action, GameState};
+
+use super::*;
+
+pub struct PlayingScene {
+    mode: GameMode,
+}
+
+impl PlayingScene {
+    pub fn new(mode: GameMode) -> PlayingScene {
+        PlayingScene { mode }
+    }
+}
+
+impl Scene for PlayingScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        context.board.resume_game();
+    }
+
+    fn update(&mut self, context: &mut SceneContext) -> Transition {
+        // restarting goes through the countdown again instead of resetting in place
+        if context.is_pressed(Action::Restart) {
+            return Transition::Switch(Box::new(CountdownScene::new(self.mode)));
+        }
+
+        // losing focus pauses the game so it doesn't keep falling in the background, the board isn't updated
+        // that frame so a pause pressed at the same time doesn't unpause it again
+        if context.has_lost_focus() {
+            context.board.pause_game();
+
+            return Transition::Switch(Box::new(PausedScene::new(self.mode)));
+        }
+
+        context.board.update(context.actions, context.audio_manager);
+        context.user_interface.update(context.actions, context.board);
+
+        if context.board.is_mode_complete() {
+            context.board.pause_game();
+
+            return Transition::Switch(Box::new(ResultsScene::new(GameResults::new(context.board, true))));
+        }
+
+        match context.board.get_game_state() {
+            GameState::RUNNING => Transition::Stay,
+            GameState::PAUSED => Transition::Switch(Box::new(PausedScene::new(self.mode))),
+            GameState::END => Transition::Switch(Box::new(ResultsScene::new(GameResults::new(context.board, false)))),
+        }
+    }
+
+    fn draw(&self, context: &mut DrawContext) {
+        context.draw_interface(true, None);
+        context.draw_board();
+    }
+}
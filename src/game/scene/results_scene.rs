@@ -0,0 +1,71 @@
+use crate::types::*;
+use crate::game::user_interface::Menu;
+
+use super::*;
+
+pub struct ResultsScene {
+    results: GameResults,
+
+    menu: Option<Menu>,
+}
+
+impl ResultsScene {
+    pub fn new(results: GameResults) -> ResultsScene {
+        ResultsScene { results, menu: None }
+    }
+}
+
+impl Scene for ResultsScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        if let Some(records) = context.records.as_mut() {
+            records.add(&self.results.stats, self.results.mode, self.results.score, self.results.finesse_faults);
+        }
+
+        // the music is turned down for as long as the game over screen is up
+        context.audio_manager.set_ducked(true);
+
+        let stats = &self.results.stats;
+
+        let lines = [
+            String::from(if self.results.completed { "FINISH" } else { "LOSERO" }),
+            String::from(self.results.mode.get_name()),
+            format!("SCORE:{}", self.results.score),
+            format!("LINES:{}", self.results.lines),
+            format!("TIME:{:.1}", stats.get_time_played() as f32 / 1000.0),
+            format!("PPS:{:.2}", stats.get_pps()),
+            format!("APM:{:.1}", stats.get_apm()),
+            format!("FAULTS:{}", self.results.finesse_faults),
+        ];
+
+        let rects: Vec<Rect> = (0..lines.len() as u32)
+            .map(|i| Rect{ x: 210, y: 100 + i * 90, width: 300, height: 70 })
+            .collect();
+
+        let texts: Vec<(&str, &Rect)> = lines.iter().map(|line| line.as_str()).zip(rects.iter()).collect();
+
+        self.menu = Some(context.open_menu(
+            &texts,
+            &[
+                (&Rect{ x: 60, y: 950, width: 280, height: 100 }, (255, 255, 255), "RETRY"),
+                (&Rect{ x: 380, y: 950, width: 280, height: 100 }, (255, 255, 255), "MENU"),
+            ],
+        ));
+    }
+
+    fn update(&mut self, context: &mut SceneContext) -> Transition {
+        match context.handle_menu(&self.menu) {
+            Some(0) => Transition::Switch(Box::new(CountdownScene::new(self.results.mode))),
+            Some(1) => Transition::Switch(Box::new(TitleScene::new())),
+            _ => Transition::Stay,
+        }
+    }
+
+    fn draw(&self, context: &mut DrawContext) {
+        context.draw_interface(false, self.menu.as_ref());
+    }
+
+    fn exit(&mut self, context: &mut SceneContext) {
+        context.audio_manager.set_ducked(false);
+        context.close_menu(&mut self.menu);
+    }
+}
@@ -0,0 +1,182 @@
+use ash::vk;
+
+use crate::{types::*, *};
+use crate::game::{audio::SoundEvent, board::{Board, GameMode}, input::{Action, Bindings}, settings::{AudioSettings, WindowSettings}, stats::{Records, Stats}, user_interface::Menu};
+
+// Everything a scene may touch while it updates
+pub struct SceneContext<'a> {
+    pub device: &'a Device,
+    pub window: &'a mut Window,
+    pub board: &'a mut Board,
+    pub user_interface: &'a mut UserInterface,
+    pub audio_manager: &'a mut AudioManager,
+    pub records: &'a mut Option<Records>,
+    pub bindings: &'a mut Bindings,
+    pub window_settings: &'a mut WindowSettings,
+    pub audio_settings: &'a mut AudioSettings,
+
+    pub events: &'a [glfw::WindowEvent],
+    // what the events mean with the current bindings
+    pub actions: &'a [Action],
+    pub curr: u128,
+    pub frame_count: u32,
+}
+
+impl SceneContext<'_> {
+    pub fn is_pressed(&self, action: Action) -> bool {
+        self.actions.contains(&action)
+    }
+
+    pub fn has_lost_focus(&self) -> bool {
+        self.events.iter().any(|event| matches!(event, glfw::WindowEvent::Focus(false)))
+    }
+
+    // raw keys, for when a scene needs the key itself rather than what it's bound to
+    pub fn get_pressed_keys(&self) -> Vec<glfw::Key> {
+        self.events
+            .iter()
+            .filter_map(|event| match *event {
+                glfw::WindowEvent::Key(key, _, glfw::Action::Press, _) => Some(key),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // builds a menu and puts its buttons on screen.
+    // Button texts can't be changed in place, so scenes showing settings build their whole menu again when one changes.
+    pub fn open_menu(&mut self, texts: &[(&str, &Rect)], buttons: &[(&Rect, Color, &str)]) -> Menu {
+        let menu = self.user_interface.create_menu(self.device, texts, buttons);
+        self.user_interface.show_menu(self.device, Some(&menu));
+
+        menu
+    }
+
+    pub fn close_menu(&mut self, menu: &mut Option<Menu>) {
+        self.user_interface.show_menu(self.device, None);
+
+        if let Some(mut menu) = menu.take() {
+            menu.destroy(self.device);
+        }
+    }
+
+    pub fn handle_menu(&mut self, menu: &Option<Menu>) -> Option<usize> {
+        let menu = menu.as_ref()?;
+
+        let previous_hovered = self.user_interface.get_hovered();
+        let clicked = self.user_interface.handle_menu(self.window, self.device, menu, self.frame_count);
+        let hovered = self.user_interface.get_hovered();
+
+        if hovered.is_some() && hovered != previous_hovered {
+            self.audio_manager.play_event(SoundEvent::MenuHover);
+        }
+
+        if clicked.is_some() {
+            self.audio_manager.play_event(SoundEvent::MenuClick);
+        }
+
+        clicked
+    }
+}
+
+// Everything a scene needs to draw, the subpasses always run in the same order
+// so the interface has to be drawn exactly once and the board (with its particles) at most once after it
+pub struct DrawContext<'a> {
+    device: &'a Device,
+    render_pass: &'a RenderPass,
+    command_buffer: &'a CommandBuffer,
+    user_interface: &'a mut UserInterface,
+    board: &'a mut Board,
+    time: u64,
+
+    board_drawn: bool,
+}
+
+impl<'a> DrawContext<'a> {
+    pub fn new(device: &'a Device, render_pass: &'a RenderPass, command_buffer: &'a CommandBuffer, user_interface: &'a mut UserInterface,
+     board: &'a mut Board, time: u64) -> DrawContext<'a> {
+        DrawContext { device, render_pass, command_buffer, user_interface, board, time, board_drawn: false }
+    }
+
+    pub fn draw_interface(&mut self, show_hud: bool, menu: Option<&Menu>) {
+        self.user_interface.draw(
+            self.device,
+            self.render_pass,
+            self.command_buffer,
+            0,
+            self.board.get_tetromino_instance_count(),
+            show_hud,
+            menu,
+        );
+    }
+
+    pub fn draw_board(&mut self) {
+        self.next_subpass();
+
+        self.board.draw(self.device, self.render_pass, self.command_buffer, 3, self.time);
+
+        self.next_subpass();
+
+        self.board.draw_particles(self.device, self.render_pass, self.command_buffer, 4);
+
+        self.board_drawn = true;
+    }
+
+    fn next_subpass(&self) {
+        unsafe {
+            self.device.get_ash_device().cmd_next_subpass(
+                self.command_buffer.get_command_buffer(),
+                vk::SubpassContents::INLINE,
+            );
+        }
+    }
+
+    // the render pass can only end on its last subpass
+    pub fn finish(self) {
+        if !self.board_drawn {
+            self.next_subpass();
+            self.next_subpass();
+        }
+    }
+}
+
+// How a game went, handed from the game to the results screen
+#[derive(Clone)]
+pub struct GameResults {
+    pub mode: GameMode,
+    pub completed: bool,
+
+    pub score: u32,
+    pub lines: u32,
+    pub finesse_faults: u32,
+    pub stats: Stats,
+}
+
+impl GameResults {
+    pub fn new(board: &Board, completed: bool) -> GameResults {
+        GameResults {
+            mode: board.get_mode(),
+            completed,
+            score: *board.get_score().lock().expect("Failed to lock the score"),
+            lines: board.get_lines_cleared(),
+            finesse_faults: board.get_finesse_faults(),
+            stats: board.get_stats().clone(),
+        }
+    }
+}
+
+pub enum Transition {
+    Stay,
+    Switch(Box<dyn Scene>),
+    Quit,
+}
+
+// One screen of the game, entered and exited exactly once each
+pub trait Scene {
+    fn enter(&mut self, _context: &mut SceneContext) {}
+
+    fn update(&mut self, context: &mut SceneContext) -> Transition;
+
+    fn draw(&self, context: &mut DrawContext);
+
+    fn exit(&mut self, _context: &mut SceneContext) {}
+}
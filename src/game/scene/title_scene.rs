@@ -0,0 +1,52 @@
+use crate::types::*;
+use crate::game::{input::Action, user_interface::Menu};
+
+use super::*;
+
+pub struct TitleScene {
+    menu: Option<Menu>,
+}
+
+impl TitleScene {
+    pub fn new() -> TitleScene {
+        TitleScene { menu: None }
+    }
+}
+
+impl Scene for TitleScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        self.menu = Some(context.open_menu(
+            &[("TETRIS", &Rect{ x: 160, y: 200, width: 400, height: 150 })],
+            &[
+                (&Rect{ x: 210, y: 480, width: 300, height: 100 }, (255, 255, 255), "PLAY"),
+                (&Rect{ x: 210, y: 610, width: 300, height: 100 }, (255, 255, 255), "CONTROLS"),
+                (&Rect{ x: 210, y: 740, width: 300, height: 100 }, (255, 255, 255), "DISPLAY"),
+                (&Rect{ x: 210, y: 870, width: 300, height: 100 }, (255, 255, 255), "AUDIO"),
+                (&Rect{ x: 210, y: 1000, width: 300, height: 100 }, (255, 255, 255), "QUIT"),
+            ],
+        ));
+    }
+
+    fn update(&mut self, context: &mut SceneContext) -> Transition {
+        if context.is_pressed(Action::Confirm) {
+            return Transition::Switch(Box::new(ModeSelectScene::new()));
+        }
+
+        match context.handle_menu(&self.menu) {
+            Some(0) => Transition::Switch(Box::new(ModeSelectScene::new())),
+            Some(1) => Transition::Switch(Box::new(ControlsScene::new())),
+            Some(2) => Transition::Switch(Box::new(DisplayScene::new())),
+            Some(3) => Transition::Switch(Box::new(AudioScene::new())),
+            Some(4) => Transition::Quit,
+            _ => Transition::Stay,
+        }
+    }
+
+    fn draw(&self, context: &mut DrawContext) {
+        context.draw_interface(false, self.menu.as_ref());
+    }
+
+    fn exit(&mut self, context: &mut SceneContext) {
+        context.close_menu(&mut self.menu);
+    }
+}
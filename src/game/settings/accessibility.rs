@@ -0,0 +1,18 @@
+use serde::Deserialize;
+use std::fs;
+
+// Settings for players who are bothered by parts of the presentation
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct Accessibility {
+    pub screen_shake: bool,
+    // scales every shake, 1 is the full configured strength
+    pub shake_intensity: f32,
+}
+
+impl Accessibility {
+    pub fn load(path: &str) -> Accessibility {
+        let contents = fs::read_to_string(path).expect("Failed to read the accessibility config file");
+
+        serde_json::from_str(&contents).expect("Could not parse accessibility JSON config")
+    }
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use super::{save_settings, PersistedConfig};
+
+// Volumes go from 0 to 100, muting keeps the volume so unmuting brings it back
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Volume {
+    pub level: u32,
+    pub muted: bool,
+}
+
+impl Volume {
+    pub fn raise(&mut self, step: u32) {
+        self.level = (self.level + step).min(100);
+    }
+
+    pub fn lower(&mut self, step: u32) {
+        self.level = self.level.min(100).saturating_sub(step);
+    }
+
+    // how loud the sound comes out, from 0 to 1
+    pub fn get_amplitude(&self) -> f32 {
+        if self.muted {
+            return 0.0;
+        }
+
+        self.level.min(100) as f32 / 100.0
+    }
+}
+
+// The master volume applies on top of each bus
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AudioSettings {
+    pub master: Volume,
+    pub music: Volume,
+    pub sfx: Volume,
+    pub ui: Volume,
+    // how many decibels the music drops by while the game is over
+    pub game_over_duck: f32,
+
+    #[serde(skip)]
+    path: String,
+}
+
+impl AudioSettings {
+    pub fn load(path: &str) -> AudioSettings {
+        let config = PersistedConfig::<AudioSettings>::load(path, "audio settings");
+
+        let mut settings = config.load_saved().unwrap_or(config.defaults);
+        settings.path = config.path;
+
+        settings
+    }
+
+    pub fn save(&self) {
+        save_settings(&self.path, self, "audio settings");
+    }
+}
@@ -0,0 +1,11 @@
+mod persisted;
+pub use persisted::{save_settings, PersistedConfig};
+
+mod accessibility;
+pub use accessibility::Accessibility;
+
+mod window_settings;
+pub use window_settings::WindowSettings;
+
+mod audio_settings;
+pub use audio_settings::{AudioSettings, Volume};
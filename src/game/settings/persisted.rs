@@ -0,0 +1,37 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fs;
+
+// The config of anything the player can change in game and have kept, `name` is only for the error messages
+#[derive(Deserialize, Debug)]
+pub struct PersistedConfig<T> {
+    // where the player's own settings are kept, the defaults are used until they change something
+    pub path: String,
+    pub defaults: T,
+
+    #[serde(skip)]
+    name: &'static str,
+}
+
+impl<T: DeserializeOwned> PersistedConfig<T> {
+    pub fn load(path: &str, name: &'static str) -> PersistedConfig<T> {
+        let contents = fs::read_to_string(path).unwrap_or_else(|_| panic!("Failed to read the {name} config file"));
+
+        let mut config: PersistedConfig<T> = serde_json::from_str(&contents).unwrap_or_else(|_| panic!("Could not parse {name} JSON config"));
+        config.name = name;
+
+        config
+    }
+
+    // None until the player has saved something, what was saved doesn't have to be the same type as the defaults
+    pub fn load_saved<S: DeserializeOwned>(&self) -> Option<S> {
+        let contents = fs::read_to_string(&self.path).ok()?;
+
+        Some(serde_json::from_str(&contents).unwrap_or_else(|_| panic!("Could not parse the {} file", self.name)))
+    }
+}
+
+pub fn save_settings<S: Serialize>(path: &str, settings: &S, name: &str) {
+    let contents = serde_json::to_string_pretty(settings).unwrap_or_else(|_| panic!("Failed to serialize the {name}"));
+
+    fs::write(path, contents).unwrap_or_else(|_| panic!("Failed to write the {name} file"));
+}
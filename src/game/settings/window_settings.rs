@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use super::{save_settings, PersistedConfig};
+use crate::WindowMode;
+
+// How the window is shown, the size is only used in windowed mode and monitors count from 0
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WindowSettings {
+    pub width: u32,
+    pub height: u32,
+    pub mode: WindowMode,
+    pub monitor: usize,
+    pub vsync: bool,
+
+    #[serde(skip)]
+    path: String,
+}
+
+impl WindowSettings {
+    pub fn load(path: &str) -> WindowSettings {
+        let config = PersistedConfig::<WindowSettings>::load(path, "window settings");
+
+        let mut settings = config.load_saved().unwrap_or(config.defaults);
+        settings.path = config.path;
+
+        settings
+    }
+
+    pub fn save(&self) {
+        save_settings(&self.path, self, "window settings");
+    }
+
+    // alt+enter switches between a window and the whole screen
+    pub fn toggle_fullscreen(&mut self) {
+        self.mode = match self.mode {
+            WindowMode::Windowed => WindowMode::Fullscreen,
+            WindowMode::Fullscreen | WindowMode::Borderless => WindowMode::Windowed,
+        };
+    }
+}
@@ -0,0 +1,5 @@
+mod snapshot;
+pub use snapshot::BoardSnapshot;
+
+mod spectator_server;
+pub use spectator_server::SpectatorServer;
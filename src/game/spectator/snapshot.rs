@@ -0,0 +1,49 @@
+use serde::Serialize;
+
+use crate::game::board::{Board, TetrominoShape};
+use crate::game::GameState;
+use crate::types::WALL_BLOCK;
+
+#[derive(Serialize)]
+pub struct PieceSnapshot {
+    pub shape: TetrominoShape,
+    pub color: [u8; 4],
+    pub cells: Vec<[u8; 2]>,
+}
+
+// Everything a spectator needs to redraw the playfield, sent as one JSON line
+#[derive(Serialize)]
+pub struct BoardSnapshot {
+    pub state: GameState,
+    pub score: u32,
+    pub grid: Vec<Vec<[u8; 4]>>,
+    pub piece: PieceSnapshot,
+}
+
+impl BoardSnapshot {
+    pub fn new(board: &Board) -> BoardSnapshot {
+        let tetromino = board.get_tetromino();
+
+        let cells = tetromino
+            .get_data()
+            .chunks(2)
+            .map(|pos| [pos[0], pos[1]])
+            .collect();
+
+        BoardSnapshot {
+            state: board.get_game_state(),
+            score: *board.get_score().lock().expect("Failed to lock the score"),
+            // the walls that narrow the field in big mode would otherwise be drawn as solid blocks
+            grid: board
+                .get_grid()
+                .iter()
+                .map(|row| row.iter().map(|block| if *block == WALL_BLOCK { [0; 4] } else { *block }).collect())
+                .collect(),
+            piece: PieceSnapshot {
+                shape: tetromino.get_shape(),
+                color: tetromino.get_color(),
+                cells,
+            },
+        }
+    }
+}
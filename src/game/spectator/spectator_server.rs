@@ -0,0 +1,132 @@
+use serde::Deserialize;
+use std::{
+    fs,
+    io::{self, ErrorKind, Write},
+    net::{TcpListener, TcpStream},
+};
+
+use super::BoardSnapshot;
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    pub enabled: bool,
+    pub address: String,
+}
+
+// A viewer's connection along with whatever of the snapshots its socket hasn't taken yet
+struct Client {
+    stream: TcpStream,
+    pending: Vec<u8>,
+}
+
+impl Client {
+    // writes as much as the socket takes without blocking, false once the viewer has hung up
+    fn flush(&mut self) -> bool {
+        while !self.pending.is_empty() {
+            match self.stream.write(&self.pending) {
+                Ok(0) => return false,
+                Ok(written) => {
+                    self.pending.drain(..written);
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => return true,
+                Err(err) if err.kind() == ErrorKind::Interrupted => (),
+                Err(_) => return false,
+            }
+        }
+
+        true
+    }
+}
+
+// a viewer this far behind isn't going to catch up
+const MAX_PENDING: usize = 1 << 20;
+
+pub struct SpectatorServer {
+    listener: TcpListener,
+    clients: Vec<Client>,
+
+    last_message: String,
+}
+
+impl SpectatorServer {
+    fn load_config(path: &str) -> Config {
+        let contents = fs::read_to_string(path).expect("Failed to read the spectator config file");
+
+        serde_json::from_str(&contents).expect("Could not parse spectator JSON config")
+    }
+
+    // the game runs without spectating if the address can't be listened on
+    pub fn from_config(path: &str) -> Option<SpectatorServer> {
+        let conf = SpectatorServer::load_config(path);
+
+        if !conf.enabled {
+            return None;
+        }
+
+        match SpectatorServer::new(&conf.address) {
+            Ok(server) => Some(server),
+            Err(err) => {
+                eprintln!("Spectating is disabled, failed to listen on {}: {err}", conf.address);
+                None
+            }
+        }
+    }
+
+    pub fn new(address: &str) -> io::Result<SpectatorServer> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+
+        Ok(SpectatorServer {
+            listener,
+            clients: Vec::new(),
+            last_message: String::new(),
+        })
+    }
+
+    pub fn accept_clients(&mut self) {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    if stream.set_nonblocking(true).is_err() {
+                        continue;
+                    }
+
+                    let _ = stream.set_nodelay(true);
+
+                    // a freshly connected viewer gets the current board right away instead of waiting for a change
+                    self.clients.push(Client {
+                        stream,
+                        pending: self.last_message.as_bytes().to_vec(),
+                    });
+                }
+
+                Err(err) if err.kind() == ErrorKind::WouldBlock => return,
+
+                Err(err) => {
+                    eprintln!("Failed to accept a spectator: {err}");
+                    return;
+                }
+            }
+        }
+    }
+
+    // only called when the board changed, so every snapshot goes out
+    pub fn publish(&mut self, snapshot: &BoardSnapshot) {
+        let mut message =
+            serde_json::to_string(snapshot).expect("Failed to serialize the board snapshot");
+        message.push('\n');
+
+        for client in &mut self.clients {
+            client.pending.extend_from_slice(message.as_bytes());
+        }
+
+        self.last_message = message;
+    }
+
+    // called every frame, whole lines go out over as many frames as the sockets need.
+    // Viewers that hung up or fell too far behind are dropped, the game never waits on them
+    pub fn flush_clients(&mut self) {
+        self.clients
+            .retain_mut(|client| client.flush() && client.pending.len() < MAX_PENDING);
+    }
+}
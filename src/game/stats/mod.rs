@@ -0,0 +1,5 @@
+mod stats;
+pub use stats::Stats;
+
+mod records;
+pub use records::Records;
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use super::Stats;
+use crate::game::board::GameMode;
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    pub enabled: bool,
+    pub path: String,
+    pub max_records: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Record {
+    #[serde(default)]
+    mode: GameMode,
+    score: u32,
+    finesse_faults: u32,
+
+    #[serde(flatten)]
+    stats: Stats,
+}
+
+// The best finished games, highest score first, kept in a JSON file between runs
+pub struct Records {
+    path: String,
+    max_records: usize,
+
+    records: Vec<Record>,
+}
+
+impl Records {
+    fn load_config(path: &str) -> Config {
+        let contents = fs::read_to_string(path).expect("Failed to read the records config file");
+
+        serde_json::from_str(&contents).expect("Could not parse records JSON config")
+    }
+
+    pub fn from_config(path: &str) -> Option<Records> {
+        let conf = Records::load_config(path);
+
+        if !conf.enabled {
+            return None;
+        }
+
+        Some(Records::new(&conf.path, conf.max_records))
+    }
+
+    // a missing records file just means nothing has been played yet, one that can't be read is started over
+    pub fn new(path: &str, max_records: usize) -> Records {
+        let records = match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|err| {
+                eprintln!("Starting the records over, could not parse the records file: {err}");
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+
+        Records {
+            path: path.to_string(),
+            max_records,
+            records,
+        }
+    }
+
+    pub fn add(&mut self, stats: &Stats, mode: GameMode, score: u32, finesse_faults: u32) {
+        self.records.push(Record {
+            mode,
+            score,
+            finesse_faults,
+            stats: stats.clone(),
+        });
+
+        self.records.sort_by(|a, b| b.score.cmp(&a.score));
+        self.records.truncate(self.max_records);
+
+        let contents = serde_json::to_string_pretty(&self.records).expect("Failed to serialize the records");
+
+        // the records are still kept for this run if they can't be saved
+        if let Err(err) = fs::write(&self.path, contents) {
+            eprintln!("Failed to write the records file: {err}");
+        }
+    }
+
+    pub fn get_high_score(&self) -> u32 {
+        self.records.first().map_or(0, |record| record.score)
+    }
+}
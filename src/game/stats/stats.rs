@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::game::board::{BoardEvent, GameState, TetrominoShape};
+
+// Garbage a clear would send in versus, the combo bonus is added on top
+fn get_clear_attack(lines: u8) -> u32 {
+    match lines {
+        0 | 1 => 0,
+        2 => 1,
+        3 => 2,
+        _ => 4,
+    }
+}
+
+fn get_combo_attack(combo: u32) -> u32 {
+    match combo {
+        0 | 1 => 0,
+        2 | 3 => 1,
+        4 | 5 => 2,
+        6 | 7 => 3,
+        8..=10 => 4,
+        _ => 5,
+    }
+}
+
+// Everything worth knowing about how a game was played, built up from the board events
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Stats {
+    time_played: u64,
+
+    pieces: u32,
+    keys: u32,
+    attack: u32,
+    lines: u32,
+
+    singles: u32,
+    doubles: u32,
+    triples: u32,
+    tetrises: u32,
+
+    max_combo: u32,
+    shape_counts: BTreeMap<TetrominoShape, u32>,
+
+    #[serde(skip)]
+    combo: u32,
+    #[serde(skip)]
+    last_piece_cleared: bool,
+    #[serde(skip)]
+    previous_update: Option<u128>,
+}
+
+impl Stats {
+    pub fn new() -> Stats {
+        Stats {
+            time_played: 0,
+            pieces: 0,
+            keys: 0,
+            attack: 0,
+            lines: 0,
+            singles: 0,
+            doubles: 0,
+            triples: 0,
+            tetrises: 0,
+            max_combo: 0,
+            shape_counts: BTreeMap::new(),
+            combo: 0,
+            last_piece_cleared: false,
+            previous_update: None,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &BoardEvent) {
+        match event {
+            BoardEvent::KeyPressed => self.keys += 1,
+
+            BoardEvent::PieceLocked(tetromino) => {
+                if !self.last_piece_cleared {
+                    self.combo = 0;
+                }
+
+                self.last_piece_cleared = false;
+                self.pieces += 1;
+                *self.shape_counts.entry(tetromino.get_shape()).or_insert(0) += 1;
+            }
+
+            BoardEvent::LinesCleared { rows, .. } => {
+                let lines = rows.len() as u8;
+
+                self.combo += 1;
+                self.max_combo = self.max_combo.max(self.combo);
+                self.last_piece_cleared = true;
+
+                self.lines += lines as u32;
+                self.attack += get_clear_attack(lines) + get_combo_attack(self.combo);
+
+                match lines {
+                    1 => self.singles += 1,
+                    2 => self.doubles += 1,
+                    3 => self.triples += 1,
+                    _ => self.tetrises += 1,
+                }
+            }
+
+            BoardEvent::PieceMoved
+            | BoardEvent::PieceRotated
+            | BoardEvent::RotationFailed
+            | BoardEvent::SoftDropped
+            | BoardEvent::HardDropped { .. }
+            | BoardEvent::FinesseFault(_)
+            | BoardEvent::GameOver => (),
+
+            BoardEvent::GameReset => *self = Stats::new(),
+        }
+    }
+
+    // the clock only runs while the game does
+    pub fn update(&mut self, curr: u128, state: GameState) {
+        if let (Some(previous), GameState::RUNNING) = (self.previous_update, state) {
+            self.time_played += curr.saturating_sub(previous) as u64;
+        }
+
+        self.previous_update = Some(curr);
+    }
+
+    pub fn get_time_played(&self) -> u64 {
+        self.time_played
+    }
+
+    pub fn get_pieces(&self) -> u32 {
+        self.pieces
+    }
+
+    pub fn get_pps(&self) -> f32 {
+        if self.time_played == 0 {
+            return 0.0;
+        }
+
+        self.pieces as f32 / (self.time_played as f32 / 1000.0)
+    }
+
+    pub fn get_apm(&self) -> f32 {
+        if self.time_played == 0 {
+            return 0.0;
+        }
+
+        self.attack as f32 / (self.time_played as f32 / 60000.0)
+    }
+
+    pub fn get_kpp(&self) -> f32 {
+        if self.pieces == 0 {
+            return 0.0;
+        }
+
+        self.keys as f32 / self.pieces as f32
+    }
+
+    pub fn get_clear_counts(&self) -> [u32; 4] {
+        [self.singles, self.doubles, self.triples, self.tetrises]
+    }
+
+    pub fn get_max_combo(&self) -> u32 {
+        self.max_combo
+    }
+
+    pub fn get_shape_count(&self, shape: TetrominoShape) -> u32 {
+        self.shape_counts.get(&shape).copied().unwrap_or(0)
+    }
+}
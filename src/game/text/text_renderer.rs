@@ -23,7 +23,7 @@ pub struct RenderInfo {
 impl<'a> TextRenderer{
     pub fn new(core: &Core, device: &Device, command_pool: &CommandPool) -> TextRenderer{
 
-        let char_count = 57;
+        let char_count = 58;
         let starting_offset = 33;
 
         let (font_atlas_tex, paddings, heights, chars_per_row, row_count)= TextRenderer::load_font_atlas(core, device, command_pool, char_count, starting_offset);
@@ -0,0 +1,36 @@
+use crate::*;
+use super::super::{text::*, button::*};
+
+// The texts and buttons of one screen, owned by whichever scene shows it
+pub struct Menu {
+    texts: Vec<Text>,
+    buttons: Vec<Button>,
+}
+
+impl Menu {
+    pub fn new(texts: Vec<Text>, buttons: Vec<Button>) -> Menu {
+        Menu { texts, buttons }
+    }
+
+    pub fn get_texts(&self) -> &Vec<Text> {
+        &self.texts
+    }
+
+    pub fn get_texts_mut(&mut self) -> &mut Vec<Text> {
+        &mut self.texts
+    }
+
+    pub fn get_buttons(&self) -> &Vec<Button> {
+        &self.buttons
+    }
+
+    pub fn destroy(&mut self, device: &Device) {
+        for text in self.texts.iter_mut() {
+            text.destroy(device);
+        }
+
+        for button in self.buttons.iter_mut() {
+            button.destroy(device);
+        }
+    }
+}
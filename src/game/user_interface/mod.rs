@@ -1,5 +1,8 @@
 mod user_interface;
 pub use user_interface::UserInterface;
 
+mod menu;
+pub use menu::Menu;
+
 mod backdrop;
-use backdrop::Backdrop;
\ No newline at end of file
+use backdrop::Backdrop;
@@ -1,11 +1,11 @@
-use crate::{game::board::Board, types::*, *};
+use crate::{game::{board::{Board, TetrominoShape}, input::Action}, types::*, *};
 use ash::vk;
 use bytemuck::bytes_of;
 use descriptor::{DescriptorInfo, DescriptorSet};
 use std::{pin::Pin, sync::{Arc, Mutex}};
 use super::super::{text::*, button::*};
 
-use super::Backdrop;
+use super::{Backdrop, Menu};
 
 
 pub struct UserInterface {
@@ -18,18 +18,34 @@ pub struct UserInterface {
     backdrop: Backdrop,
 
     score_text: Text,
-    end_text: Text,
+    finesse_text: Text,
+    stats_texts: Vec<Text>,
 
     button_manager: ButtonManager,
-    reset_button: Button,
 
     last_pressed: bool,
+    // the button under the cursor, so a sound can be played when it moves onto another one
+    hovered: Option<usize>,
 
-    game_state: GameState,
+    score: Arc<Mutex<u32>>,
+    finesse_faults: u32,
 
-    score: Arc<Mutex<u32>>
+    show_stats: bool,
+    stats_strings: Vec<String>,
 }
 
+const SHAPES: [TetrominoShape; 7] = [
+    TetrominoShape::I,
+    TetrominoShape::J,
+    TetrominoShape::L,
+    TetrominoShape::O,
+    TetrominoShape::S,
+    TetrominoShape::T,
+    TetrominoShape::Z,
+];
+
+const STATS_LINE_COUNT: u32 = 16;
+
 impl<'a> UserInterface {
     pub fn new(core: &Core, device: &Device, command_pool: &CommandPool, score: Arc<Mutex<u32>>) -> UserInterface {
         let mut text_manager = TextManager::new(core, device, command_pool);
@@ -38,37 +54,35 @@ impl<'a> UserInterface {
 
         let mut texts = text_manager.create_texts(device, &[
             ("0", &Rect{ x: 100, y: 850, width: 100, height: 100 }),
-            ("LOSERO", &Rect{ x: 150, y: 300, width: 200, height: 200 }),
+            ("FAULTS:0", &Rect{ x: 100, y: 1000, width: 200, height: 50 }),
         ]);
 
         let score_text = texts.remove(0);
-        let end_text = texts.remove(0);
-
-        let backdrop = Backdrop::new(device, command_pool, "background.png");
-
-        let mut button_manager = ButtonManager::new(device, command_pool);
+        let finesse_text = texts.remove(0);
 
-        let mut buttons = button_manager.create_buttons(device, &[(&Rect{x: 400, y: 900, width: 300, height: 100}, (255, 255, 255), "RESET")],
-         &text_manager.get_text_renderer());
+        let stats_rects: Vec<Rect> = (0..STATS_LINE_COUNT).map(|i| Rect{ x: 520, y: 20 + i * 50, width: 180, height: 40 }).collect();
+        let stats_infos: Vec<(&str, &Rect)> = stats_rects.iter().map(|rect| ("0", rect)).collect();
+        let stats_texts = text_manager.create_texts(device, &stats_infos);
 
-        let reset_button = buttons.remove(0);
+        let backdrop = Backdrop::new(device, command_pool, "background.png");
 
-        button_manager.add_button(&reset_button);
-        button_manager.update(device);
-        button_manager.update_press_states(device, vec![reset_button.get_name()], 0);
+        let button_manager = ButtonManager::new(device, command_pool);
 
         UserInterface {
             vertex_buffer: buffers.0,
             index_buffer: buffers.1,
             text_manager,
             score_text,
-            end_text,
-            game_state: GameState::RUNNING,
+            finesse_text,
+            stats_texts,
             score,
             backdrop,
             button_manager,
-            reset_button,
-            last_pressed: false
+            last_pressed: false,
+            hovered: None,
+            finesse_faults: 0,
+            show_stats: false,
+            stats_strings: Vec::new(),
         }
     }
 
@@ -128,7 +142,36 @@ impl<'a> UserInterface {
         (vertex_buffer, index_buffer)
     }
 
-    fn handle_buttons(&mut self, window: &Window, device: &Device, board: &mut Board, frame_count: u32) {
+    pub fn create_menu(&mut self, device: &Device, texts: &[(&str, &Rect)], buttons: &[(&Rect, Color, &str)]) -> Menu {
+        let texts = self.text_manager.create_texts(device, texts);
+        let buttons = self.button_manager.create_buttons(device, buttons, &self.text_manager.get_text_renderer());
+
+        Menu::new(texts, buttons)
+    }
+
+    // only one menu's buttons are on screen at a time
+    pub fn show_menu(&mut self, device: &Device, menu: Option<&Menu>) {
+        self.button_manager.clear_data();
+
+        let Some(menu) = menu else {
+            return;
+        };
+
+        for button in menu.get_buttons() {
+            self.button_manager.add_button(button);
+        }
+
+        if !menu.get_buttons().is_empty() {
+            self.button_manager.update(device);
+        }
+    }
+
+    pub fn change_menu_text(&mut self, device: &Device, menu: &mut Menu, index: usize, text: &str) {
+        self.text_manager.change_texts(device, &mut [(&mut menu.get_texts_mut()[index], text)]);
+    }
+
+    // returns the index of the button that was clicked this frame
+    pub fn handle_menu(&mut self, window: &Window, device: &Device, menu: &Menu, frame_count: u32) -> Option<usize> {
         let mouse_state = window.get_window_handle().get_mouse_button(glfw::MouseButton::Button1);
         let is_pressed = mouse_state == glfw::Action::Press;
 
@@ -138,43 +181,86 @@ impl<'a> UserInterface {
             is_clicked = true;
         }
 
-        let (x, y) = window.get_window_handle().get_cursor_pos();
-        let mouse_pos = (x as u32, y as u32);
+        let mouse_pos = window.get_cursor_canvas_pos();
 
         let mut pressed_names = Vec::<&String>::new();
+        let mut clicked = None;
+        let mut hovered = None;
 
-        if self.reset_button.is_on_cursor(mouse_pos) {
-            if is_clicked {
-                board.reset_game();
+        for (i, button) in menu.get_buttons().iter().enumerate() {
+            // nothing is hovered while the cursor is on the bars around the canvas
+            if !mouse_pos.is_some_and(|pos| button.is_on_cursor(pos)) {
+                continue;
             }
 
-            pressed_names.push(self.reset_button.get_name());
+            if is_clicked {
+                clicked = Some(i);
+            }
 
+            hovered = hovered.or(Some(i));
+            pressed_names.push(button.get_name());
         }
             
         self.button_manager.update_press_states(device, pressed_names, frame_count);
 
 
         self.last_pressed = is_pressed;
+        self.hovered = hovered;
+
+        clicked
+    }
+
+    pub fn get_hovered(&self) -> Option<usize> {
+        self.hovered
+    }
+
+    fn get_stats_strings(board: &Board) -> Vec<String> {
+        let stats = board.get_stats();
+        let clears = stats.get_clear_counts();
+
+        let mut strings = vec![
+            format!("PPS:{:.2}", stats.get_pps()),
+            format!("APM:{:.1}", stats.get_apm()),
+            format!("KPP:{:.2}", stats.get_kpp()),
+            format!("TIME:{}", stats.get_time_played() / 1000),
+            format!("COMBO:{}", stats.get_max_combo()),
+            format!("SINGLE:{}", clears[0]),
+            format!("DOUBLE:{}", clears[1]),
+            format!("TRIPLE:{}", clears[2]),
+            format!("TETRIS:{}", clears[3]),
+        ];
+
+        strings.extend(SHAPES.iter().map(|shape| format!("{:?}:{}", shape, stats.get_shape_count(*shape))));
+
+        strings
     }
 
-    pub fn update(&mut self, state: GameState, window: &Window, device: &Device, board: &mut Board, frame_count: u32) {
-        self.handle_buttons(window, device, board, frame_count);
+    pub fn update(&mut self, actions: &[Action], board: &Board) {
+        if actions.contains(&Action::ToggleStats) {
+            self.show_stats = !self.show_stats;
+        }
+
+        self.finesse_faults = board.get_finesse_faults();
 
-        self.game_state = state;
+        if self.show_stats {
+            self.stats_strings = UserInterface::get_stats_strings(board);
+        }
     }
 
 
+    // the score and stats only make sense next to the board, menus draw on top of whatever is shown
     pub fn draw(
         &mut self,
         device: &Device,
         render_pass: &RenderPass,
         command_buffer: &CommandBuffer,
         subpass_index: u32,
-        tetromino_instance_count: u32
+        tetromino_instance_count: u32,
+        show_hud: bool,
+        menu: Option<&Menu>
     ) {
         self.backdrop.draw(device, render_pass, command_buffer, subpass_index, tetromino_instance_count, &self.vertex_buffer, &self.index_buffer);
-        self.draw_texts(device, render_pass, command_buffer, subpass_index+1);
+        self.draw_texts(device, render_pass, command_buffer, subpass_index+1, show_hud, menu);
         self.button_manager.draw(device, command_buffer, &self.vertex_buffer, &self.index_buffer, render_pass, subpass_index+2);
 
     }
@@ -184,19 +270,50 @@ impl<'a> UserInterface {
         device: &Device,
         render_pass: &RenderPass,
         command_buffer: &CommandBuffer,
-        subpass_index: u32) {
+        subpass_index: u32,
+        show_hud: bool,
+        menu: Option<&Menu>) {
+
+        if show_hud {
+            let finesse_string = format!("FAULTS:{}", self.finesse_faults);
 
-        self.text_manager.change_texts(device, &mut [(&mut self.score_text, &(*self.score.lock().expect("Failed to lock mutex womp womp").to_string()))]);
+            self.text_manager.change_texts(device, &mut [(&mut self.score_text, &(*self.score.lock().expect("Failed to lock mutex womp womp").to_string())),
+             (&mut self.finesse_text, &finesse_string)]);
+        }
+
+        if show_hud && self.show_stats {
+            let mut stats_changes: Vec<(&mut Text, &str)> = self.stats_texts.iter_mut()
+                .zip(self.stats_strings.iter())
+                .map(|(text, string)| (text, string.as_str()))
+                .collect();
+
+            self.text_manager.change_texts(device, &mut stats_changes);
+        }
 
         self.text_manager.get_text_renderer().prepare_text_renderer(device, command_buffer, &self.vertex_buffer, &self.index_buffer,
              render_pass, subpass_index);
-            
-        self.score_text.draw(device, command_buffer, &self.text_manager.get_text_renderer(), render_pass);
 
-        self.reset_button.draw_text(device, &self.text_manager.get_text_renderer(), command_buffer, render_pass);
+        if show_hud {
+            self.score_text.draw(device, command_buffer, &self.text_manager.get_text_renderer(), render_pass);
+            self.finesse_text.draw(device, command_buffer, &self.text_manager.get_text_renderer(), render_pass);
 
-        if matches!(self.game_state, GameState::END) {
-            self.end_text.draw(device, command_buffer, &self.text_manager.get_text_renderer(), render_pass);
+            if self.show_stats {
+                for text in &self.stats_texts {
+                    text.draw(device, command_buffer, &self.text_manager.get_text_renderer(), render_pass);
+                }
+            }
+        }
+
+        let Some(menu) = menu else {
+            return;
+        };
+
+        for text in menu.get_texts() {
+            text.draw(device, command_buffer, &self.text_manager.get_text_renderer(), render_pass);
+        }
+
+        for button in menu.get_buttons() {
+            button.draw_text(device, &self.text_manager.get_text_renderer(), command_buffer, render_pass);
         }
     }
 
@@ -289,9 +406,11 @@ impl<'a> UserInterface {
         self.index_buffer.destroy(device);
         self.backdrop.destroy(device);
         self.score_text.destroy(device);
-        self.end_text.destroy(device);
+        self.finesse_text.destroy(device);
+        for text in self.stats_texts.iter_mut() {
+            text.destroy(device);
+        }
         self.text_manager.destroy(device);
         self.button_manager.destroy(device);
-        self.reset_button.destroy(device);
     }
 }
@@ -0,0 +1,16 @@
+#![allow(dead_code)]
+#![warn(clippy::pedantic)]
+
+pub mod game;
+use game::*;
+
+mod window;
+use window::*;
+
+mod canvas;
+use canvas::*;
+
+mod vulkan;
+use vulkan::*;
+
+mod types;
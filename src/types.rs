@@ -3,6 +3,8 @@ use ash::vk;
 pub const PLAYFIELD_WIDTH: usize = 10;
 pub const PLAYFIELD_HEIGHT: usize = 16;
 pub type Grid = [[[u8; 4]; PLAYFIELD_WIDTH as usize]; PLAYFIELD_HEIGHT as usize];
+// fills the cells pieces can't go into when the field is smaller than the grid, never drawn
+pub const WALL_BLOCK: [u8; 4] = [1, 1, 1, 0];
 pub type VertexInputData = (Vec<vk::VertexInputBindingDescription>, Vec<vk::VertexInputAttributeDescription>);
 pub type Color = (u8, u8, u8); // todo replace all instances
 
@@ -182,8 +182,9 @@ impl Buffer {
         command_buffer: &mut CommandBuffer,
         data: &[u8],
     ) {
-        if data.len() != self.size as usize {
-            eprintln!("WARNING: Trying to update buffer but the data provided is a different size");
+        // updating only the start of a buffer is fine, going past its end isn't
+        if data.len() > self.size as usize {
+            eprintln!("WARNING: Trying to update buffer but the data provided is larger than it");
         }
 
 
@@ -12,11 +12,11 @@ pub struct Core {
 }
 
 impl Core {
-    pub fn new(window: &Window) -> Core {
+    pub fn new(window: &Window, vsync: bool) -> Core {
         let entry = ash::Entry::linked();
         let instance = Instance::new(&entry, window);
         let mut device = Device::new(instance.get_ash_instance());
-        let swapchain = Swapchain::new(&entry, window, &instance, &mut device);
+        let swapchain = Swapchain::new(&entry, window, &instance, &mut device, vsync);
 
         Core {
             entry,
@@ -42,6 +42,10 @@ impl Core {
         &self.swapchain
     }
     
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.swapchain.set_vsync(vsync);
+    }
+
     pub fn recreate_swapchain(&mut self, window: &Window) {
         self.swapchain.recreate(window, &self.instance, &mut self.device);
     }
@@ -12,6 +12,7 @@ pub struct Swapchain {
     surface_instance: khr::surface::Instance,
     image_views: Vec<vk::ImageView>,
     swapchain_info: SwapchainInfo,
+    vsync: bool,
 }
 
 pub struct SwapchainInfo {
@@ -35,6 +36,21 @@ impl Swapchain {
         formats[0]
     }
 
+    // FIFO is the only mode every device has, without vsync mailbox is preferred since it doesn't tear
+    fn choose_present_mode(present_modes: Vec<vk::PresentModeKHR>, vsync: bool) -> vk::PresentModeKHR {
+        if vsync {
+            return vk::PresentModeKHR::FIFO;
+        }
+
+        for preferred in [vk::PresentModeKHR::MAILBOX, vk::PresentModeKHR::IMMEDIATE] {
+            if present_modes.contains(&preferred) {
+                return preferred;
+            }
+        }
+
+        vk::PresentModeKHR::FIFO
+    }
+
     fn create_swapchain(
         window: &Window,
         instance: &Instance,
@@ -42,7 +58,8 @@ impl Swapchain {
         surface_khr: vk::SurfaceKHR,
         surface_instance: &khr::surface::Instance,
         format: vk::SurfaceFormatKHR,
-        old_swapchain: vk::SwapchainKHR
+        old_swapchain: vk::SwapchainKHR,
+        vsync: bool,
     ) -> SwapchainInfo {
         let capabilities = unsafe {
             surface_instance.get_physical_device_surface_capabilities(
@@ -72,6 +89,12 @@ impl Swapchain {
             capabilities.current_extent
         };
 
+        let present_mode = Swapchain::choose_present_mode(unsafe {
+            surface_instance
+                .get_physical_device_surface_present_modes(device.get_vk_physical_device(), surface_khr)
+                .expect("Failed to get the physical device surface present modes")
+        }, vsync);
+
         let image_count = if capabilities.max_image_count != 0 {
             clamp(
                 PREFERRED_IMAGE_COUNT,
@@ -95,7 +118,7 @@ impl Swapchain {
             p_queue_family_indices: &device.get_queue_family_index(),
             pre_transform: capabilities.current_transform,
             composite_alpha: vk::CompositeAlphaFlagsKHR::OPAQUE,
-            present_mode: vk::PresentModeKHR::FIFO,
+            present_mode,
             clipped: true as u32, // because yes
             old_swapchain: old_swapchain,
             image_format: format.format,
@@ -162,6 +185,7 @@ impl Swapchain {
         window: &Window,
         instance: &Instance,
         device: &mut Device,
+        vsync: bool,
     ) -> Swapchain {
         let mut surface_khr: vk::SurfaceKHR = vk::SurfaceKHR::null();
 
@@ -190,7 +214,8 @@ impl Swapchain {
             surface_khr,
             &surface_instance,
             format,
-            vk::SwapchainKHR::null()
+            vk::SwapchainKHR::null(),
+            vsync,
         );
         let image_views = Swapchain::create_swapchain_image_views(
             device,
@@ -204,16 +229,33 @@ impl Swapchain {
             surface_instance,
             swapchain_info,
             image_views,
+            vsync,
         }
     }
 
+    // the old swapchain is handed over to the new one and destroyed after, so it can't be in use anymore
     pub fn recreate(&mut self, window: &Window, instance: &Instance, device: &mut Device) {
+        let old_swapchain = self.swapchain_info.swapchain;
+
         self.swapchain_info = Swapchain::create_swapchain(window, instance, device, self.surface_khr, &self.surface_instance,
-        self.swapchain_info.format, self.swapchain_info.swapchain);
+        self.swapchain_info.format, old_swapchain, self.vsync);
+
+        unsafe {
+            for image_view in self.image_views.drain(..) {
+                device.get_ash_device().destroy_image_view(image_view, None);
+            }
+
+            self.swapchain_info.swapchain_device.destroy_swapchain(old_swapchain, None);
+        }
 
         self.image_views = Swapchain::create_swapchain_image_views(device, &self.swapchain_info.swapchain_device, self.swapchain_info.swapchain, self.swapchain_info.format.format);
     }
 
+    // takes effect when the swapchain is recreated
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.vsync = vsync;
+    }
+
     pub fn get_swapchain_info(&self) -> &SwapchainInfo {
         &self.swapchain_info
     }
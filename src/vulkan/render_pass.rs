@@ -296,8 +296,8 @@ impl RenderPass {
             ..Default::default()
         };
 
-        let dynamic_states =
-            [/*vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR cant be assed lmao*/];
+        // set every frame so the pipelines don't have to be rebuilt when the swapchain is resized
+        let dynamic_states = [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
 
         let dynamic_state = vk::PipelineDynamicStateCreateInfo {
             s_type: vk::StructureType::PIPELINE_DYNAMIC_STATE_CREATE_INFO,
@@ -511,6 +511,14 @@ impl RenderPass {
         swapchain: &Swapchain,
     ) {
 
+        for framebuffer in self.framebuffers.drain(..) {
+            if framebuffer != vk::Framebuffer::null() {
+                unsafe {
+                    device.get_ash_device().destroy_framebuffer(framebuffer, None);
+                }
+            }
+        }
+
         self.color_image.image.destroy(device);
         self.depth_image.image.destroy(device);
 
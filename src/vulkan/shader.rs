@@ -8,16 +8,9 @@ pub struct Shader {
 
 impl Shader {
     fn read_binary(path: String) -> Vec<u8> {
-        let bytes = std::fs::read(&path);
-        let bytes_unwrapped: Vec<u8> = match bytes {
-            Ok(val) => val,
-            Err(err) => {
-                eprintln!("Failed to open file {} because {}", &path, err);
-                return Vec::new();
-            }
-        };
-
-        bytes_unwrapped
+        // a missing binary would only fail later as an empty shader module
+        std::fs::read(&path)
+            .unwrap_or_else(|err| panic!("Failed to open file {} because {}", &path, err))
     }
 
     fn create_shader_module(
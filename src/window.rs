@@ -1,32 +1,69 @@
 use glfw::WindowEvent;
+use serde::{Deserialize, Serialize};
 
-pub struct Window {
-    glfw_context: glfw::Glfw,
-    events: glfw::GlfwReceiver<(f64, WindowEvent)>,
-    window_handle: glfw::PWindow,
-    width: u32,
-    height: u32,
+use crate::canvas::Letterbox;
+
+// Borderless covers the whole monitor with an undecorated window instead of taking it over like fullscreen does
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum WindowMode {
+    Windowed,
+    Fullscreen,
+    Borderless,
+}
+
+// The framebuffer size in pixels (not the window size, which differs on high DPI screens) and whether the
+// swapchain still has to catch up with it. Any number of resizes between two frames end up as one recreation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FramebufferState {
+    extent: (u32, u32),
+    resized: bool,
 }
 
-static mut WIDTH: u32 = 0u32;
-static mut HEIGHT: u32 = 0u32;
+impl FramebufferState {
+    pub fn new(extent: (u32, u32)) -> FramebufferState {
+        FramebufferState { extent, resized: false }
+    }
+
+    pub fn handle_resize(&mut self, width: i32, height: i32) {
+        let extent = (width.max(0) as u32, height.max(0) as u32);
+
+        if extent != self.extent {
+            self.extent = extent;
+            self.resized = true;
+        }
+    }
+
+    // for when the swapchain turns out to be out of date without the size changing
+    pub fn request_resize(&mut self) {
+        self.resized = true;
+    }
 
-fn size_callback(_window: &mut glfw::Window, new_width: i32, new_height: i32) {
+    // a minimised window has no pixels, so nothing can be presented to it until it's restored
+    pub fn is_minimized(&self) -> bool {
+        self.extent.0 == 0 || self.extent.1 == 0
+    }
 
-    if new_width == 0 || new_height == 0 {
-        unsafe{
-            WIDTH = 1;
-            HEIGHT = 1;
+    // true once per pending resize, held back while minimised since a swapchain can't be empty
+    pub fn take_resize(&mut self) -> bool {
+        if self.is_minimized() {
+            return false;
         }
-        return;
+
+        std::mem::take(&mut self.resized)
     }
 
-    unsafe{
-        WIDTH = new_width as u32;
-        HEIGHT = new_height as u32;
+    pub fn get_extent(&self) -> (u32, u32) {
+        self.extent
     }
 }
 
+pub struct Window {
+    glfw_context: glfw::Glfw,
+    events: glfw::GlfwReceiver<(f64, WindowEvent)>,
+    window_handle: glfw::PWindow,
+    framebuffer: FramebufferState,
+}
+
 impl Window {
 
     fn error_callback(err: glfw::Error, description: String) {
@@ -37,44 +74,98 @@ impl Window {
         let mut context = glfw::init(Window::error_callback).expect("");
 
         context.window_hint(glfw::WindowHint::ClientApi(glfw::ClientApiHint::NoApi));
+        // the size is grown by the monitor's content scale so the game is as large on a high DPI screen
+        context.window_hint(glfw::WindowHint::ScaleToMonitor(true));
 
         let (mut window, events) = context
             .create_window(width, height, title, glfw::WindowMode::Windowed)
             .expect("Failed to create a GLFW window");
 
         window.set_key_polling(true);
-        window.set_size_callback(size_callback);
+        window.set_focus_polling(true);
+        window.set_framebuffer_size_polling(true);
 
-        unsafe {
-            WIDTH = width;
-            HEIGHT = height;
-        }
+        let (framebuffer_width, framebuffer_height) = window.get_framebuffer_size();
 
         Window {
             glfw_context: context,
             events,
             window_handle: window,
-            width,
-            height,
+            framebuffer: FramebufferState::new((framebuffer_width.max(0) as u32, framebuffer_height.max(0) as u32)),
         }
     }
 
-    pub fn update_size(&self) {
-        let new_size = self.window_handle.get_size();
+    // the window only cares about its own size, every event is still passed on to the game
+    pub fn handle_events(&mut self, events: &[WindowEvent]) {
+        for event in events {
+            if let WindowEvent::FramebufferSize(width, height) = *event {
+                self.framebuffer.handle_resize(width, height);
+            }
+        }
+    }
 
-        if new_size.0 == 0 || new_size.1 == 0 {
-            unsafe{
-                WIDTH = 1;
-                HEIGHT = 1;
-            } 
+    pub fn request_resize(&mut self) {
+        self.framebuffer.request_resize();
+    }
 
-            return;
-        }
+    pub fn take_resize(&mut self) -> bool {
+        self.framebuffer.take_resize()
+    }
 
-        unsafe{
-            WIDTH = new_size.0 as u32;
-            HEIGHT = new_size.0 as u32;
-        }
+    pub fn is_minimized(&self) -> bool {
+        self.framebuffer.is_minimized()
+    }
+
+    // fullscreen and borderless use the monitor's own resolution, the size is only for windowed mode.
+    // Monitors that aren't connected fall back to the primary one.
+    pub fn set_mode(&mut self, mode: WindowMode, monitor: usize, size: (u32, u32)) {
+        let window_handle = &mut self.window_handle;
+
+        self.glfw_context.with_connected_monitors(|_, monitors| {
+            let Some(monitor) = monitors.get(monitor).or(monitors.first()) else {
+                return;
+            };
+
+            let Some(video_mode) = monitor.get_video_mode() else {
+                return;
+            };
+
+            let (x, y) = monitor.get_pos();
+
+            match mode {
+                WindowMode::Windowed => {
+                    window_handle.set_decorated(true);
+                    window_handle.set_monitor(
+                        glfw::WindowMode::Windowed,
+                        x + (video_mode.width as i32 - size.0 as i32).max(0) / 2,
+                        y + (video_mode.height as i32 - size.1 as i32).max(0) / 2,
+                        size.0,
+                        size.1,
+                        None,
+                    );
+                }
+
+                WindowMode::Fullscreen => {
+                    window_handle.set_monitor(
+                        glfw::WindowMode::FullScreen(&**monitor),
+                        0,
+                        0,
+                        video_mode.width,
+                        video_mode.height,
+                        Some(video_mode.refresh_rate),
+                    );
+                }
+
+                WindowMode::Borderless => {
+                    window_handle.set_decorated(false);
+                    window_handle.set_monitor(glfw::WindowMode::Windowed, x, y, video_mode.width, video_mode.height, None);
+                }
+            }
+        });
+    }
+
+    pub fn get_monitor_count(&mut self) -> usize {
+        self.glfw_context.with_connected_monitors(|_, monitors| monitors.len())
     }
 
     pub fn get_window_handle(&self) -> &glfw::Window {
@@ -98,8 +189,81 @@ impl Window {
     }
 
     pub fn get_extent(&self) -> (u32, u32) {
-        unsafe{
-            (WIDTH, HEIGHT)
-        }
+        self.framebuffer.get_extent()
+    }
+
+    pub fn get_letterbox(&self) -> Letterbox {
+        Letterbox::new(self.framebuffer.get_extent())
+    }
+
+    pub fn get_cursor_canvas_pos(&self) -> Option<(u32, u32)> {
+        get_canvas_pos(self.window_handle.get_cursor_pos(), self.window_handle.get_size(), self.framebuffer.get_extent())
+    }
+}
+
+// the cursor is in screen coordinates, which are only framebuffer pixels when the content scale is 1
+fn get_canvas_pos(cursor: (f64, f64), window_size: (i32, i32), framebuffer: (u32, u32)) -> Option<(u32, u32)> {
+    let (width, height) = window_size;
+
+    if width <= 0 || height <= 0 {
+        return None;
+    }
+
+    let pixel_pos = (
+        cursor.0 * f64::from(framebuffer.0) / f64::from(width),
+        cursor.1 * f64::from(framebuffer.1) / f64::from(height),
+    );
+
+    Letterbox::new(framebuffer).to_canvas(pixel_pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_canvas_pos, FramebufferState};
+
+    #[test]
+    fn resizes_are_handled_once_and_not_while_minimized() {
+        let mut state = FramebufferState::new((720, 1280));
+        assert!(!state.take_resize());
+
+        // the same size again isn't a resize
+        state.handle_resize(720, 1280);
+        assert!(!state.take_resize());
+
+        // several events before the next frame only recreate the swapchain once, at the last size
+        state.handle_resize(800, 1000);
+        state.handle_resize(1080, 1920);
+        assert_eq!(state.get_extent(), (1080, 1920));
+        assert!(state.take_resize());
+        assert!(!state.take_resize());
+
+        // minimising holds the resize back until the window has pixels again
+        state.handle_resize(0, 0);
+        assert!(state.is_minimized());
+        assert!(!state.take_resize());
+
+        state.handle_resize(1080, 1920);
+        assert!(!state.is_minimized());
+        assert!(state.take_resize());
+        assert!(!state.take_resize());
+
+        // an out of date swapchain is recreated even though the size didn't change
+        state.request_resize();
+        assert!(state.take_resize());
+        assert!(!state.take_resize());
+    }
+
+    #[test]
+    fn the_cursor_is_scaled_to_framebuffer_pixels() {
+        // at a content scale of 2 the window is half as many screen coordinates as it has pixels
+        assert_eq!(get_canvas_pos((180.0, 320.0), (360, 640), (720, 1280)), Some((360, 640)));
+        assert_eq!(get_canvas_pos((180.0, 320.0), (720, 1280), (720, 1280)), Some((180, 320)));
+
+        // the middle of a pillarboxed landscape window is still the middle of the canvas
+        assert_eq!(get_canvas_pos((480.0, 270.0), (960, 540), (1920, 1080)), Some((360, 640)));
+        assert_eq!(get_canvas_pos((100.0, 270.0), (960, 540), (1920, 1080)), None);
+
+        // a minimized window has no size to scale by
+        assert_eq!(get_canvas_pos((10.0, 10.0), (0, 0), (0, 0)), None);
     }
 }